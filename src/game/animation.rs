@@ -58,6 +58,13 @@ pub struct Animations {
 #[derive(Component)]
 pub struct Flippable;
 
+#[derive(Component)]
+pub struct Flash {
+    pub now: f32,
+    pub max: f32,
+    pub color: Color,
+}
+
 pub fn animation(
     time: Res<Time>,
     mut query: Query<(&mut Animations, &mut Handle<Image>)>,
@@ -107,3 +114,20 @@ pub fn flip(mut query: Query<(&mut Sprite, &Velocity), With<Flippable>>) {
         }
     }
 }
+
+pub fn flash(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut Flash, &mut Sprite)>,
+) {
+    for (entity, mut flash, mut sprite) in query.iter_mut() {
+        flash.now += time.delta_seconds();
+
+        if flash.now >= flash.max {
+            let alpha = sprite.color.a();
+            sprite.color = flash.color;
+            sprite.color.set_a(alpha);
+            commands.entity(entity).remove::<Flash>();
+        }
+    }
+}
@@ -0,0 +1,200 @@
+use std::f32::consts::PI;
+
+use bevy::prelude::*;
+use bevy_kira_audio::Audio;
+
+use crate::plugin::camera::GameCamera;
+
+use super::{
+    enemy::{Dead, Enemy, HitFeedback, Score, Spawning},
+    entity::{GameEntity, GAME_LAYER},
+    health::{Health, Resistance},
+    pattern::{Gun, Pattern, Shape},
+    physics::{Body, Knockback, Velocity},
+    player::Player,
+    weapon::Modifiers,
+};
+
+const BOSS_COLOR: Color = Color::rgb(0.6, 0.8, 1.0);
+const BOSS_SIZE: f32 = 384.0;
+const BOSS_HEALTH: u32 = 60;
+const BOSS_EXPLOSION_RESISTANCE: f32 = 0.5;
+const BOSS_SLOW_TIME: f32 = 2.0;
+const BOSS_FREEZE_TIME: f32 = 1.0;
+const BOSS_SCORE: u32 = 50;
+const BOSS_INTERVAL: f32 = 180.0;
+const BOSS_PHASE_COUNT: u32 = 3;
+
+pub struct BossSchedule {
+    pub active: bool,
+    now: f32,
+    max: f32,
+}
+
+#[derive(Component)]
+pub struct Boss {
+    speed: f32,
+    impulse: f32,
+    current: u32,
+}
+
+fn health_phase(health: &Health) -> u32 {
+    ((health.max - health.current) * BOSS_PHASE_COUNT / health.max)
+        .min(BOSS_PHASE_COUNT - 1)
+}
+
+fn gun_for_phase(phase: u32) -> Gun {
+    match phase {
+        0 => Gun::new(
+            Pattern {
+                shape: Shape::Spread { count: 3, angle: PI / 6.0 },
+                speed: 640.0,
+                lifetime: None,
+            },
+            1.5,
+        ),
+        1 => Gun::new(
+            Pattern {
+                shape: Shape::Ring { count: 16 },
+                speed: 512.0,
+                lifetime: None,
+            },
+            2.0,
+        )
+        .with_modifiers(Modifiers {
+            slow: BOSS_SLOW_TIME,
+            ..Default::default()
+        }),
+        _ => Gun::new(
+            Pattern {
+                shape: Shape::Spiral { count: 4, step: PI / 16.0 },
+                speed: 768.0,
+                lifetime: Some(3.0),
+            },
+            0.25,
+        )
+        .with_modifiers(Modifiers {
+            freeze: BOSS_FREEZE_TIME,
+            ..Default::default()
+        }),
+    }
+}
+
+pub fn init(mut commands: Commands) {
+    commands.insert_resource(BossSchedule {
+        active: false,
+        now: 0.0,
+        max: BOSS_INTERVAL,
+    });
+}
+
+pub fn schedule(
+    mut commands: Commands,
+    mut schedule: ResMut<BossSchedule>,
+    time: Res<Time>,
+    asset_server: Res<AssetServer>,
+    query: Query<(&Transform, &OrthographicProjection), With<GameCamera>>,
+) {
+    if schedule.active {
+        return;
+    }
+
+    schedule.now += time.delta_seconds();
+
+    if schedule.now >= schedule.max {
+        let (camera_transform, projection) = query.single();
+
+        schedule.active = true;
+        schedule.now = 0.0;
+
+        commands
+            .spawn_bundle(SpriteBundle {
+                sprite: Sprite {
+                    color: BOSS_COLOR * [1.0, 1.0, 1.0, 0.0],
+                    custom_size: Some(Vec2::new(BOSS_SIZE, BOSS_SIZE)),
+                    ..Default::default()
+                },
+                transform: Transform::from_xyz(
+                    camera_transform.translation.x
+                        + (projection.left + projection.right) / 2.0,
+                    camera_transform.translation.y + projection.top - BOSS_SIZE,
+                    GAME_LAYER,
+                ),
+                texture: asset_server.load("enemy/walker/move.png"),
+                ..Default::default()
+            })
+            .insert(GameEntity)
+            .insert(Spawning { now: 0.0, max: 3.0 })
+            .insert(Enemy)
+            .insert(Health {
+                resistance: Resistance {
+                    explosion: BOSS_EXPLOSION_RESISTANCE,
+                    ..Default::default()
+                },
+                ..Health::new(BOSS_HEALTH)
+            })
+            .insert(Boss { speed: 192.0, impulse: 2048.0, current: 0 })
+            .insert(gun_for_phase(0))
+            .insert(HitFeedback {
+                flash: 0.05,
+                knockback: 0.0,
+                ..Default::default()
+            });
+    }
+}
+
+pub fn movement(
+    audio: Res<Audio>,
+    asset_server: Res<AssetServer>,
+    player_query: Query<&Transform, With<Player>>,
+    mut boss_query: Query<
+        (&mut Velocity, &Transform, &Body, &Health, &Boss),
+        (Without<Dead>, Without<Knockback>),
+    >,
+) {
+    let player_transform = if let Ok(result) = player_query.get_single() {
+        result
+    } else {
+        return;
+    };
+
+    for (mut velocity, boss_transform, body, health, boss) in
+        boss_query.iter_mut()
+    {
+        let phase = health_phase(health);
+
+        velocity.0.x = (player_transform.translation.x
+            - boss_transform.translation.x)
+            .signum()
+            * boss.speed
+            * (1.0 + phase as f32 * 0.5);
+
+        if phase == BOSS_PHASE_COUNT - 1 && body.bottom {
+            audio.play(asset_server.load("spring.wav"));
+            velocity.0.y = boss.impulse;
+        }
+    }
+}
+
+pub fn phase(mut query: Query<(&mut Boss, &mut Gun, &Health)>) {
+    for (mut boss, mut gun, health) in query.iter_mut() {
+        let phase = health_phase(health);
+
+        if boss.current != phase {
+            boss.current = phase;
+            *gun = gun_for_phase(phase);
+        }
+    }
+}
+
+pub fn death(
+    mut score: ResMut<Score>,
+    mut schedule: ResMut<BossSchedule>,
+    query: Query<&Boss, Added<Dead>>,
+) {
+    for _ in query.iter() {
+        score.0 += BOSS_SCORE;
+        schedule.active = false;
+        schedule.now = 0.0;
+    }
+}
@@ -0,0 +1,217 @@
+use std::f32::consts::PI;
+
+use bevy::prelude::*;
+use rand::{seq::SliceRandom, Rng};
+
+use super::{
+    boss::Boss,
+    enemy::{
+        self, Charger, Dead, Enemy, Jumper, Score, Shooter, Spawning, Splitter,
+        Walker,
+    },
+    health::Health,
+    weapon::Modifiers,
+};
+
+const ELITE_CHANCE_MIN: f32 = 0.05;
+const ELITE_CHANCE_MAX: f32 = 0.5;
+const ELITE_CHANCE_TIME: f32 = 600.0;
+const ELITE_SCORE: u32 = 2;
+const ELITE_ARMOR: u32 = 2;
+const ELITE_HASTE: f32 = 1.5;
+const ELITE_EXPLOSIVE_BURN: f32 = 2.0;
+const ELITE_AFFIXES: [Affix; 5] = [
+    Affix::Armored,
+    Affix::Hasted,
+    Affix::Regenerating,
+    Affix::Shielded,
+    Affix::Explosive,
+];
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Affix {
+    Armored,
+    Hasted,
+    Regenerating,
+    Shielded,
+    Explosive,
+}
+
+impl Affix {
+    fn color(self) -> Color {
+        match self {
+            Affix::Armored => Color::rgb(0.6, 0.6, 0.8),
+            Affix::Hasted => Color::rgb(1.0, 1.0, 0.4),
+            Affix::Regenerating => Color::rgb(0.4, 1.0, 0.4),
+            Affix::Shielded => Color::rgb(0.4, 1.0, 1.0),
+            Affix::Explosive => Color::rgb(1.0, 0.4, 0.3),
+        }
+    }
+}
+
+pub struct Elites {
+    now: f32,
+}
+
+#[derive(Component)]
+pub struct Elite {
+    pub affixes: Vec<Affix>,
+}
+
+#[derive(Component)]
+pub struct Hasted;
+
+#[derive(Component)]
+pub struct Regenerating {
+    now: f32,
+    max: f32,
+}
+
+#[derive(Component)]
+pub struct Shielded;
+
+#[derive(Component)]
+pub struct Explosive {
+    count: u32,
+    speed: f32,
+}
+
+pub fn init(mut commands: Commands) {
+    commands.insert_resource(Elites { now: 0.0 });
+}
+
+pub fn promote(
+    mut commands: Commands,
+    mut elites: ResMut<Elites>,
+    time: Res<Time>,
+    mut query: Query<
+        (Entity, &mut Health, &mut Sprite),
+        (With<Enemy>, Added<Spawning>, Without<Boss>),
+    >,
+) {
+    elites.now += time.delta_seconds();
+
+    let chance = (ELITE_CHANCE_MIN
+        + (ELITE_CHANCE_MAX - ELITE_CHANCE_MIN) * elites.now
+            / ELITE_CHANCE_TIME)
+        .min(ELITE_CHANCE_MAX);
+
+    for (entity, mut health, mut sprite) in query.iter_mut() {
+        if !rand::thread_rng().gen_bool(chance as f64) {
+            continue;
+        }
+
+        let count = rand::thread_rng().gen_range(1..=2);
+        let affixes: Vec<Affix> = ELITE_AFFIXES
+            .choose_multiple(&mut rand::thread_rng(), count)
+            .cloned()
+            .collect();
+
+        let mut elite = commands.entity(entity);
+
+        for affix in affixes.iter() {
+            sprite.color *= affix.color().as_rgba_f32();
+
+            match affix {
+                Affix::Armored => {
+                    health.current += ELITE_ARMOR;
+                    health.max += ELITE_ARMOR;
+                }
+                Affix::Hasted => {
+                    elite.insert(Hasted);
+                }
+                Affix::Regenerating => {}
+                Affix::Shielded => {
+                    elite.insert(Shielded);
+                }
+                Affix::Explosive => {
+                    elite.insert(Explosive { count: 8, speed: 512.0 });
+                }
+            }
+        }
+
+        if affixes.contains(&Affix::Regenerating) {
+            elite.insert(Regenerating { now: 0.0, max: 3.0 });
+        }
+
+        elite.insert(Elite { affixes });
+    }
+}
+
+pub fn haste(
+    mut walker_query: Query<&mut Walker, Added<Hasted>>,
+    mut shooter_query: Query<&mut Shooter, Added<Hasted>>,
+    mut jumper_query: Query<&mut Jumper, Added<Hasted>>,
+    mut charger_query: Query<&mut Charger, Added<Hasted>>,
+    mut splitter_query: Query<&mut Splitter, Added<Hasted>>,
+) {
+    for mut walker in walker_query.iter_mut() {
+        walker.acceleration *= ELITE_HASTE;
+    }
+
+    for mut shooter in shooter_query.iter_mut() {
+        shooter.speed *= ELITE_HASTE;
+    }
+
+    for mut jumper in jumper_query.iter_mut() {
+        jumper.speed *= ELITE_HASTE;
+    }
+
+    for mut charger in charger_query.iter_mut() {
+        charger.speed *= ELITE_HASTE;
+        charger.dash_speed *= ELITE_HASTE;
+    }
+
+    for mut splitter in splitter_query.iter_mut() {
+        splitter.speed *= ELITE_HASTE;
+    }
+}
+
+pub fn regenerate(
+    time: Res<Time>,
+    mut query: Query<(&mut Health, &mut Regenerating), Without<Dead>>,
+) {
+    for (mut health, mut regenerating) in query.iter_mut() {
+        if health.current >= health.max {
+            regenerating.now = 0.0;
+            continue;
+        }
+
+        regenerating.now += time.delta_seconds();
+
+        while regenerating.now >= regenerating.max
+            && health.current < health.max
+        {
+            health.heal(1);
+            regenerating.now -= regenerating.max;
+        }
+    }
+}
+
+pub fn death(
+    mut commands: Commands,
+    mut score: ResMut<Score>,
+    asset_server: Res<AssetServer>,
+    query: Query<(&Transform, &Elite, Option<&Explosive>), Added<Dead>>,
+) {
+    for (transform, elite, explosive) in query.iter() {
+        score.0 += ELITE_SCORE * elite.affixes.len() as u32;
+
+        if let Some(explosive) = explosive {
+            for index in 0..explosive.count {
+                enemy::spawn_bullet(
+                    &mut commands,
+                    &asset_server,
+                    transform.translation,
+                    index as f32 * 2.0 * PI / explosive.count as f32,
+                    explosive.speed,
+                    Some(1.0),
+                    Modifiers {
+                        burn: ELITE_EXPLOSIVE_BURN,
+                        ..Default::default()
+                    },
+                );
+            }
+        }
+    }
+}
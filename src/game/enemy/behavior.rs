@@ -0,0 +1,183 @@
+use bevy::prelude::*;
+
+use crate::game::{
+    health::Health,
+    pattern::Gun,
+    physics::{Body, Knockback, Velocity},
+    player::Player,
+    status::StatusEffects,
+};
+
+use super::Dead;
+
+#[derive(Clone, Copy)]
+pub enum Behavior {
+    Patrol { speed: f32 },
+    Chase { speed: f32 },
+    Flee { speed: f32 },
+    JumpWhenBlocked { impulse: f32 },
+    Shoot,
+    ShootWhenAligned { tolerance: f32 },
+}
+
+#[derive(Clone, Copy)]
+pub enum Condition {
+    After(f32),
+    PlayerWithin(f32),
+    PlayerBeyond(f32),
+    PlayerLost,
+    Blocked,
+    HealthBelow(u32),
+}
+
+#[derive(Clone, Copy)]
+pub struct Transition {
+    pub condition: Condition,
+    pub target: usize,
+}
+
+#[derive(Clone)]
+pub struct State {
+    pub behaviors: Vec<Behavior>,
+    pub transitions: Vec<Transition>,
+}
+
+#[derive(Component)]
+pub struct Brain {
+    states: Vec<State>,
+    current: usize,
+    direction: f32,
+    now: f32,
+}
+
+impl Brain {
+    pub fn new(states: Vec<State>) -> Self {
+        Brain { states, current: 0, direction: 1.0, now: 0.0 }
+    }
+}
+
+struct Perception {
+    offset: Option<Vec2>,
+    blocked: bool,
+    health: u32,
+}
+
+impl Condition {
+    fn check(self, perception: &Perception, now: f32) -> bool {
+        match self {
+            Condition::After(max) => now >= max,
+            Condition::PlayerWithin(distance) => perception
+                .offset
+                .is_some_and(|offset| offset.length() <= distance),
+            Condition::PlayerBeyond(distance) => perception
+                .offset
+                .is_some_and(|offset| offset.length() > distance),
+            Condition::PlayerLost => perception.offset.is_none(),
+            Condition::Blocked => perception.blocked,
+            Condition::HealthBelow(health) => perception.health < health,
+        }
+    }
+}
+
+pub fn brain(
+    time: Res<Time>,
+    player_query: Query<&Transform, With<Player>>,
+    mut enemy_query: Query<
+        (
+            &mut Brain,
+            &mut Velocity,
+            Option<&mut Gun>,
+            &Transform,
+            &Body,
+            &Health,
+            Option<&StatusEffects>,
+        ),
+        (Without<Dead>, Without<Knockback>),
+    >,
+) {
+    let player_transform = player_query.get_single().ok();
+
+    for (
+        mut brain,
+        mut velocity,
+        mut gun,
+        enemy_transform,
+        body,
+        health,
+        effects,
+    ) in enemy_query.iter_mut()
+    {
+        let multiplier = effects.map_or(1.0, StatusEffects::speed);
+        let perception = Perception {
+            offset: player_transform.map(|player_transform| {
+                (player_transform.translation - enemy_transform.translation)
+                    .truncate()
+            }),
+            blocked: body.left && brain.direction < 0.0
+                || body.right && brain.direction > 0.0,
+            health: health.current,
+        };
+
+        brain.now += time.delta_seconds();
+
+        let next = brain.states[brain.current]
+            .transitions
+            .iter()
+            .find(|transition| {
+                transition.condition.check(&perception, brain.now)
+            })
+            .map(|transition| transition.target);
+
+        if let Some(next) = next {
+            brain.current = next;
+            brain.now = 0.0;
+        }
+
+        velocity.0.x = 0.0;
+
+        if let Some(gun) = gun.as_mut() {
+            gun.active = false;
+        }
+
+        for index in 0..brain.states[brain.current].behaviors.len() {
+            match brain.states[brain.current].behaviors[index] {
+                Behavior::Patrol { speed } => {
+                    if perception.blocked {
+                        brain.direction = -brain.direction;
+                    }
+
+                    velocity.0.x = brain.direction * speed * multiplier;
+                }
+                Behavior::Chase { speed } => {
+                    if let Some(offset) = perception.offset {
+                        brain.direction = offset.x.signum();
+                        velocity.0.x = brain.direction * speed * multiplier;
+                    }
+                }
+                Behavior::Flee { speed } => {
+                    if let Some(offset) = perception.offset {
+                        brain.direction = -offset.x.signum();
+                        velocity.0.x = brain.direction * speed * multiplier;
+                    }
+                }
+                Behavior::JumpWhenBlocked { impulse } => {
+                    if perception.blocked && body.bottom {
+                        velocity.0.y = impulse;
+                    }
+                }
+                Behavior::Shoot => {
+                    if let Some(gun) = gun.as_mut() {
+                        gun.active = true;
+                    }
+                }
+                Behavior::ShootWhenAligned { tolerance } => {
+                    if let (Some(gun), Some(offset)) =
+                        (gun.as_mut(), perception.offset)
+                    {
+                        gun.active = offset.y.abs() <= tolerance;
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,1210 @@
+use bevy::{
+    ecs::system::SystemParam,
+    prelude::*,
+    sprite::collide_aabb::{self, Collision},
+};
+use bevy_kira_audio::Audio;
+use rand::Rng;
+
+use crate::plugin::camera::GameCamera;
+
+use behavior::{Behavior, Brain, Condition, State, Transition};
+
+use super::{
+    animation::{Animation, Animations, Flash, Flippable, LoadAnimation},
+    boss::BossSchedule,
+    entity::{GameEntity, GAME_LAYER},
+    health::{DamageType, Died, Health},
+    melee::{self, Melee},
+    pattern::{Gun, Pattern, Shape},
+    physics::{
+        Acceleration, Body, Cleanup, Knockback, Lifetime, Velocity, GRAVITY,
+        TIME_STEP,
+    },
+    player::Player,
+    settings::Settings,
+    status::{self, StatusEffects},
+    weapon::Modifiers,
+};
+
+pub mod behavior;
+
+const ENEMY_JUMPER_FALL_ANIMATION: usize = 0;
+const ENEMY_JUMPER_JUMP_ANIMATION: usize = 1;
+const ENEMY_STOMP_BOUNCE: f32 = 1536.0;
+const ENEMY_STOMP_SCORE: u32 = 2;
+const ENEMY_DYING_TIME: f32 = 0.75;
+const ENEMY_DYING_SPIN: f32 = 8.0;
+const ENEMY_SIZE: f32 = 128.0;
+const ENEMY_SPAWN_ATTEMPTS: u32 = 16;
+const ENEMY_WARNING_COLOR: Color = Color::rgba(1.0, 0.0, 0.0, 0.5);
+const ENEMY_WARNING_TIME: f32 = 1.0;
+const ENEMY_SHOOTER_PATTERNS: [Pattern; 3] = [
+    Pattern { shape: Shape::Forward, speed: 512.0, lifetime: None },
+    Pattern { shape: Shape::Aimed, speed: 512.0, lifetime: None },
+    Pattern {
+        shape: Shape::Burst { count: 3, interval: 0.15 },
+        speed: 640.0,
+        lifetime: None,
+    },
+];
+const ENEMY_CHARGER_COLOR: Color = Color::rgb(1.0, 0.75, 0.5);
+const ENEMY_CHARGER_FLASH_FREQUENCY: f32 = 1.0 / 8.0;
+const ENEMY_SPLITTER_COLOR: Color = Color::rgb(0.5, 1.0, 0.5);
+const ENEMY_SPLITTER_SCALE: f32 = 0.7;
+const ENEMY_SPLITTER_SPEEDUP: f32 = 1.5;
+const ENEMY_NEST_COLOR: Color = Color::rgb(0.45, 0.3, 0.15);
+const ENEMY_NEST_SIZE: f32 = 192.0;
+const ENEMY_SOLDIER_COLOR: Color = Color::rgb(0.8, 0.6, 1.0);
+
+pub struct SpawnInterval {
+    now: f32,
+    min: f32,
+    max: f32,
+}
+
+pub struct Score(pub u32);
+
+#[derive(Component)]
+pub struct Spawning {
+    pub now: f32,
+    pub max: f32,
+}
+
+#[derive(Component)]
+pub struct Walker {
+    pub acceleration: f32,
+}
+
+#[derive(Component)]
+pub struct Shooter {
+    pub speed: f32,
+}
+
+#[derive(Component)]
+pub struct Jumper {
+    impulse: f32,
+    pub speed: f32,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ChargerState {
+    Idle,
+    Windup,
+    Dash,
+    Recover,
+    Stunned,
+}
+
+#[derive(Component)]
+pub struct Charger {
+    pub speed: f32,
+    pub dash_speed: f32,
+    state: ChargerState,
+    color: Color,
+    target: f32,
+    direction: f32,
+    now: f32,
+    idle: f32,
+    windup: f32,
+    dash: f32,
+    recover: f32,
+    stun: f32,
+}
+
+#[derive(Component)]
+pub struct Splitter {
+    pub speed: f32,
+    generation: u32,
+    count: u32,
+}
+
+#[derive(Component)]
+pub struct Nest {
+    budget: u32,
+    now: f32,
+    max: f32,
+}
+
+#[derive(Component)]
+pub struct Minion(Entity);
+
+#[derive(Component)]
+pub struct Bullet {
+    pub modifiers: Modifiers,
+}
+
+#[derive(Component)]
+pub struct Enemy;
+
+#[derive(Component)]
+pub struct HitFeedback {
+    pub flash: f32,
+    pub knockback: f32,
+    pub hit_stop: bool,
+}
+
+impl Default for HitFeedback {
+    fn default() -> Self {
+        HitFeedback { flash: 0.1, knockback: 1.0, hit_stop: true }
+    }
+}
+
+#[derive(Component)]
+pub struct Stompable {
+    pub damage: u32,
+    now: f32,
+    max: f32,
+}
+
+impl Default for Stompable {
+    fn default() -> Self {
+        Stompable { damage: 1, now: 0.2, max: 0.2 }
+    }
+}
+
+#[derive(Component)]
+pub struct Dead;
+
+#[derive(Component)]
+pub struct Dying {
+    now: f32,
+    max: f32,
+}
+
+pub fn init(mut commands: Commands) {
+    commands.insert_resource(SpawnInterval { now: 0.0, min: 0.5, max: 7.5 });
+    commands.insert_resource(Score(0));
+}
+
+pub fn prespawn(
+    mut commands: Commands,
+    mut spawn_interval: ResMut<SpawnInterval>,
+    boss_schedule: Res<BossSchedule>,
+    spawn_rules: SpawnRules,
+    time: Res<Time>,
+    audio: Res<Audio>,
+    asset_server: Res<AssetServer>,
+) {
+    if boss_schedule.active {
+        return;
+    }
+
+    spawn_interval.now += time.delta_seconds();
+
+    while spawn_interval.now >= spawn_interval.max {
+        let kind = rand::thread_rng().gen_range(1..=27);
+        let size = if kind == 25 { ENEMY_NEST_SIZE } else { ENEMY_SIZE };
+        let position = spawn_rules.position(size);
+        let now = if spawn_rules.settings.spawn_warning {
+            audio.play(asset_server.load("warning.wav"));
+            commands
+                .spawn_bundle(SpriteBundle {
+                    sprite: Sprite {
+                        color: ENEMY_WARNING_COLOR,
+                        custom_size: Some(Vec2::new(size, size)),
+                        ..Default::default()
+                    },
+                    transform: Transform::from_translation(
+                        position.extend(GAME_LAYER),
+                    ),
+                    ..Default::default()
+                })
+                .insert(GameEntity)
+                .insert(Lifetime { now: 0.0, max: ENEMY_WARNING_TIME });
+
+            -ENEMY_WARNING_TIME
+        } else {
+            0.0
+        };
+
+        match kind {
+            1..=15 => {
+                commands
+                    .spawn_bundle(SpriteBundle {
+                        sprite: Sprite {
+                            color: Color::rgba(1.0, 1.0, 1.0, 0.0),
+                            custom_size: Some(Vec2::new(
+                                ENEMY_SIZE, ENEMY_SIZE,
+                            )),
+                            ..Default::default()
+                        },
+                        transform: Transform::from_translation(
+                            position.extend(GAME_LAYER),
+                        ),
+                        texture: asset_server.load("enemy/walker/move.png"),
+                        ..Default::default()
+                    })
+                    .insert(GameEntity)
+                    .insert(Spawning { now, max: 1.0 })
+                    .insert(Enemy)
+                    .insert(Health::new(1))
+                    .insert(Stompable::default())
+                    .insert(Walker { acceleration: 768.0 });
+            }
+            16..=19 => {
+                commands
+                    .spawn_bundle(SpriteBundle {
+                        sprite: Sprite {
+                            color: Color::rgba(1.0, 1.0, 1.0, 0.0),
+                            custom_size: Some(Vec2::new(
+                                ENEMY_SIZE, ENEMY_SIZE,
+                            )),
+                            ..Default::default()
+                        },
+                        transform: Transform::from_translation(
+                            position.extend(GAME_LAYER),
+                        ),
+                        texture: asset_server.load("enemy/shooter/move.png"),
+                        ..Default::default()
+                    })
+                    .insert(GameEntity)
+                    .insert(Spawning { now, max: 1.5 })
+                    .insert(Enemy)
+                    .insert(Health::new(2))
+                    .insert(Stompable::default())
+                    .insert(Shooter { speed: 256.0 })
+                    .insert(Gun::new(
+                        ENEMY_SHOOTER_PATTERNS[rand::thread_rng()
+                            .gen_range(0..ENEMY_SHOOTER_PATTERNS.len())],
+                        2.0,
+                    ));
+            }
+            20 => {
+                commands
+                    .spawn_bundle(SpriteBundle {
+                        sprite: Sprite {
+                            color: Color::rgba(1.0, 1.0, 1.0, 0.0),
+                            custom_size: Some(Vec2::new(
+                                ENEMY_SIZE,
+                                ENEMY_SIZE * 1.5,
+                            )),
+                            ..Default::default()
+                        },
+                        transform: Transform::from_translation(
+                            position.extend(GAME_LAYER),
+                        ),
+                        ..Default::default()
+                    })
+                    .insert(GameEntity)
+                    .insert(Animations {
+                        animations: vec![
+                            Animation {
+                                textures: asset_server
+                                    .load_animation("enemy/jumper/fall", 1),
+                                ..Default::default()
+                            },
+                            Animation {
+                                textures: asset_server
+                                    .load_animation("enemy/jumper/jump", 3),
+                                max: 1.0 / 10.0,
+                                next: Some(ENEMY_JUMPER_FALL_ANIMATION),
+                                ..Default::default()
+                            },
+                        ],
+                        ..Default::default()
+                    })
+                    .insert(Spawning { now, max: 1.0 })
+                    .insert(Enemy)
+                    .insert(Health::new(3))
+                    .insert(Jumper { impulse: 2560.0, speed: 512.0 });
+            }
+            21..=22 => {
+                commands
+                    .spawn_bundle(SpriteBundle {
+                        sprite: Sprite {
+                            color: ENEMY_CHARGER_COLOR * [1.0, 1.0, 1.0, 0.0],
+                            custom_size: Some(Vec2::new(
+                                ENEMY_SIZE, ENEMY_SIZE,
+                            )),
+                            ..Default::default()
+                        },
+                        transform: Transform::from_translation(
+                            position.extend(GAME_LAYER),
+                        ),
+                        texture: asset_server.load("enemy/walker/move.png"),
+                        ..Default::default()
+                    })
+                    .insert(GameEntity)
+                    .insert(Spawning { now, max: 1.0 })
+                    .insert(Enemy)
+                    .insert(Health::new(2))
+                    .insert(Stompable::default())
+                    .insert(Charger {
+                        speed: 256.0,
+                        dash_speed: 2048.0,
+                        state: ChargerState::Idle,
+                        color: ENEMY_CHARGER_COLOR,
+                        target: 0.0,
+                        direction: 0.0,
+                        now: 0.0,
+                        idle: 2.0,
+                        windup: 0.75,
+                        dash: 1.0,
+                        recover: 0.5,
+                        stun: 1.5,
+                    });
+            }
+            23..=24 => {
+                commands
+                    .spawn_bundle(SpriteBundle {
+                        sprite: Sprite {
+                            color: ENEMY_SPLITTER_COLOR * [1.0, 1.0, 1.0, 0.0],
+                            custom_size: Some(Vec2::new(
+                                ENEMY_SIZE, ENEMY_SIZE,
+                            )),
+                            ..Default::default()
+                        },
+                        transform: Transform::from_translation(
+                            position.extend(GAME_LAYER),
+                        ),
+                        texture: asset_server.load("enemy/walker/move.png"),
+                        ..Default::default()
+                    })
+                    .insert(GameEntity)
+                    .insert(Spawning { now, max: 1.0 })
+                    .insert(Enemy)
+                    .insert(Health::new(3))
+                    .insert(Stompable::default())
+                    .insert(Splitter { speed: 256.0, generation: 2, count: 2 });
+            }
+            25 => {
+                commands
+                    .spawn_bundle(SpriteBundle {
+                        sprite: Sprite {
+                            color: ENEMY_NEST_COLOR * [1.0, 1.0, 1.0, 0.0],
+                            custom_size: Some(Vec2::new(
+                                ENEMY_NEST_SIZE,
+                                ENEMY_NEST_SIZE,
+                            )),
+                            ..Default::default()
+                        },
+                        transform: Transform::from_translation(
+                            position.extend(GAME_LAYER),
+                        ),
+                        ..Default::default()
+                    })
+                    .insert(GameEntity)
+                    .insert(Spawning { now, max: 2.0 })
+                    .insert(Enemy)
+                    .insert(Health::new(10))
+                    .insert(Stompable::default())
+                    .insert(Nest { budget: 8, now: 0.0, max: 3.0 })
+                    .insert(HitFeedback {
+                        knockback: 0.0,
+                        ..Default::default()
+                    });
+            }
+            26..=27 => {
+                commands
+                    .spawn_bundle(SpriteBundle {
+                        sprite: Sprite {
+                            color: ENEMY_SOLDIER_COLOR * [1.0, 1.0, 1.0, 0.0],
+                            custom_size: Some(Vec2::new(
+                                ENEMY_SIZE, ENEMY_SIZE,
+                            )),
+                            ..Default::default()
+                        },
+                        transform: Transform::from_translation(
+                            position.extend(GAME_LAYER),
+                        ),
+                        texture: asset_server.load("enemy/shooter/move.png"),
+                        ..Default::default()
+                    })
+                    .insert(GameEntity)
+                    .insert(Spawning { now, max: 1.0 })
+                    .insert(Enemy)
+                    .insert(Health::new(3))
+                    .insert(Stompable::default())
+                    .insert(Gun::new(
+                        Pattern {
+                            shape: Shape::Forward,
+                            speed: 768.0,
+                            lifetime: None,
+                        },
+                        1.0,
+                    ))
+                    .insert(Brain::new(vec![
+                        State {
+                            behaviors: vec![Behavior::Patrol { speed: 192.0 }],
+                            transitions: vec![Transition {
+                                condition: Condition::PlayerWithin(768.0),
+                                target: 1,
+                            }],
+                        },
+                        State {
+                            behaviors: vec![
+                                Behavior::Chase { speed: 320.0 },
+                                Behavior::JumpWhenBlocked { impulse: 1536.0 },
+                                Behavior::ShootWhenAligned { tolerance: 64.0 },
+                            ],
+                            transitions: vec![
+                                Transition {
+                                    condition: Condition::HealthBelow(2),
+                                    target: 2,
+                                },
+                                Transition {
+                                    condition: Condition::PlayerBeyond(1024.0),
+                                    target: 0,
+                                },
+                                Transition {
+                                    condition: Condition::PlayerLost,
+                                    target: 0,
+                                },
+                            ],
+                        },
+                        State {
+                            behaviors: vec![
+                                Behavior::Flee { speed: 384.0 },
+                                Behavior::Shoot,
+                            ],
+                            transitions: vec![
+                                Transition {
+                                    condition: Condition::Blocked,
+                                    target: 1,
+                                },
+                                Transition {
+                                    condition: Condition::After(3.0),
+                                    target: 1,
+                                },
+                            ],
+                        },
+                    ]));
+            }
+            _ => unreachable!(),
+        }
+
+        spawn_interval.now -= spawn_interval.max;
+        spawn_interval.max =
+            (spawn_interval.max * 0.975).max(spawn_interval.min);
+    }
+}
+
+#[derive(SystemParam)]
+pub struct SpawnRules<'w, 's> {
+    settings: Res<'w, Settings>,
+    camera_query: Query<
+        'w,
+        's,
+        (&'static Transform, &'static OrthographicProjection),
+        With<GameCamera>,
+    >,
+    player_query: Query<'w, 's, &'static Transform, With<Player>>,
+}
+
+impl<'w, 's> SpawnRules<'w, 's> {
+    fn position(&self, size: f32) -> Vec2 {
+        let (camera_transform, projection) = self.camera_query.single();
+
+        let camera_bounds = Rect {
+            left: camera_transform.translation.x + projection.left,
+            bottom: camera_transform.translation.y + projection.bottom,
+            right: camera_transform.translation.x + projection.right,
+            top: camera_transform.translation.y + projection.top,
+        };
+
+        let player_position =
+            self.player_query.get_single().ok().map(|player_transform| {
+                player_transform.translation.truncate()
+            });
+
+        spawn_position(&self.settings, &camera_bounds, player_position, size)
+    }
+}
+
+fn spawn_position(
+    settings: &Settings,
+    camera_bounds: &Rect<f32>,
+    player_position: Option<Vec2>,
+    size: f32,
+) -> Vec2 {
+    let mut rng = rand::thread_rng();
+    let mut farthest: Option<(Vec2, f32)> = None;
+
+    for _ in 0..ENEMY_SPAWN_ATTEMPTS {
+        let x = rng
+            .gen_range(camera_bounds.left + size..camera_bounds.right - size);
+        let y = rng
+            .gen_range(camera_bounds.bottom + size..camera_bounds.top - size);
+
+        let position = match (settings.spawn_at_edges, settings.spawn_in_sky) {
+            (true, true) if rng.gen_bool(0.5) => {
+                Vec2::new(x, camera_bounds.top - size)
+            }
+            (true, _) => {
+                if rng.gen_bool(0.5) {
+                    Vec2::new(camera_bounds.left + size, y)
+                } else {
+                    Vec2::new(camera_bounds.right - size, y)
+                }
+            }
+            (false, true) => Vec2::new(x, camera_bounds.top - size),
+            (false, false) => Vec2::new(x, y),
+        };
+
+        let (min_distance, player_position) =
+            match (settings.spawn_min_distance, player_position) {
+                (Some(min_distance), Some(player_position)) => {
+                    (min_distance, player_position)
+                }
+                _ => return position,
+            };
+        let distance = position.distance(player_position);
+
+        if distance >= min_distance {
+            return position;
+        }
+
+        if farthest.map_or(true, |(_, farthest)| distance > farthest) {
+            farthest = Some((position, distance));
+        }
+    }
+
+    farthest.map_or(Vec2::ZERO, |(position, _)| position)
+}
+
+pub fn spawn_bullet(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    translation: Vec3,
+    angle: f32,
+    speed: f32,
+    lifetime: Option<f32>,
+    modifiers: Modifiers,
+) {
+    let direction = Vec2::new(angle.cos(), angle.sin());
+
+    let mut bullet = commands.spawn_bundle(SpriteBundle {
+        sprite: Sprite {
+            custom_size: Some(Vec2::new(64.0, 28.0)),
+            ..Default::default()
+        },
+        transform: Transform::from_translation(translation).with_rotation(
+            Quat::from_rotation_z(
+                direction.y.atan2(direction.x.abs()) * direction.x.signum(),
+            ),
+        ),
+        texture: asset_server.load("enemy/shooter/bullet.png"),
+        ..Default::default()
+    });
+
+    bullet
+        .insert(GameEntity)
+        .insert(Flippable)
+        .insert(Velocity(direction * speed))
+        .insert(Cleanup)
+        .insert(Bullet { modifiers });
+
+    if let Some(max) = lifetime {
+        bullet.insert(Lifetime { now: 0.0, max });
+    }
+}
+
+pub fn spawn(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut Sprite, &mut Spawning)>,
+) {
+    for (entity, mut sprite, mut spawning) in query.iter_mut() {
+        spawning.now += time.delta_seconds();
+
+        if spawning.now >= spawning.max {
+            sprite.color.set_a(1.0);
+
+            commands
+                .entity(entity)
+                .remove::<Spawning>()
+                .insert(Flippable)
+                .insert(Acceleration(Vec2::new(0.0, GRAVITY)))
+                .insert(Velocity::default())
+                .insert(Body::default());
+        } else {
+            sprite.color.set_a(spawning.now.max(0.0) / spawning.max);
+        }
+    }
+}
+
+pub fn walker(
+    player_query: Query<&Transform, With<Player>>,
+    mut enemy_query: Query<
+        (&mut Acceleration, &Transform, &Walker, Option<&StatusEffects>),
+        Without<Dead>,
+    >,
+) {
+    let player_transform = if let Ok(result) = player_query.get_single() {
+        result
+    } else {
+        return;
+    };
+
+    for (mut acceleration, enemy_transform, walker, effects) in
+        enemy_query.iter_mut()
+    {
+        acceleration.0.x = (player_transform.translation.x
+            - enemy_transform.translation.x)
+            .signum()
+            * walker.acceleration
+            * effects.map_or(1.0, StatusEffects::speed);
+    }
+}
+
+pub fn shooter(
+    player_query: Query<&Transform, With<Player>>,
+    mut enemy_query: Query<
+        (&mut Velocity, &Transform, &Shooter, Option<&StatusEffects>),
+        (Without<Dead>, Without<Knockback>),
+    >,
+) {
+    let player_transform = if let Ok(result) = player_query.get_single() {
+        result
+    } else {
+        return;
+    };
+
+    for (mut velocity, enemy_transform, shooter, effects) in
+        enemy_query.iter_mut()
+    {
+        velocity.0.x = (player_transform.translation.x
+            - enemy_transform.translation.x)
+            .signum()
+            * shooter.speed
+            * effects.map_or(1.0, StatusEffects::speed);
+    }
+}
+
+pub fn jumper(
+    audio: Res<Audio>,
+    asset_server: Res<AssetServer>,
+    camera_query: Query<
+        (&Transform, &OrthographicProjection),
+        With<GameCamera>,
+    >,
+    mut enemy_query: Query<
+        (
+            &mut Velocity,
+            &mut Animations,
+            &Transform,
+            &Body,
+            &Jumper,
+            Option<&StatusEffects>,
+        ),
+        Without<Dead>,
+    >,
+) {
+    let (camera_transform, projection) = camera_query.single();
+
+    let camera_bounds = Rect {
+        left: camera_transform.translation.x + projection.left,
+        bottom: camera_transform.translation.y + projection.bottom,
+        right: camera_transform.translation.x + projection.right,
+        top: camera_transform.translation.y + projection.top,
+    };
+
+    for (
+        mut velocity,
+        mut animations,
+        enemy_transform,
+        body,
+        jumper,
+        effects,
+    ) in enemy_query.iter_mut()
+    {
+        let speed = jumper.speed * effects.map_or(1.0, StatusEffects::speed);
+
+        if velocity.0.x == 0.0 {
+            if enemy_transform.translation.x - camera_bounds.left
+                < camera_bounds.right - enemy_transform.translation.x
+            {
+                velocity.0.x = speed;
+            } else {
+                velocity.0.x = -speed;
+            }
+        }
+
+        if body.bottom {
+            velocity.0.x = velocity.0.x.signum() * speed;
+            audio.play(asset_server.load("spring.wav"));
+            velocity.0.y = jumper.impulse;
+            animations.current = ENEMY_JUMPER_JUMP_ANIMATION;
+            let current = animations.current;
+            let mut animation = &mut animations.animations[current];
+            animation.frame = 0;
+            animation.now = 0.0;
+        }
+    }
+}
+
+pub fn charger(
+    time: Res<Time>,
+    player_query: Query<&Transform, With<Player>>,
+    mut enemy_query: Query<
+        (
+            &mut Velocity,
+            &mut Charger,
+            &mut Sprite,
+            &Transform,
+            &Body,
+            Option<&StatusEffects>,
+            Option<&Flash>,
+        ),
+        (Without<Dead>, Without<Knockback>),
+    >,
+) {
+    let player_transform = if let Ok(result) = player_query.get_single() {
+        result
+    } else {
+        return;
+    };
+
+    for (
+        mut velocity,
+        mut charger,
+        mut sprite,
+        enemy_transform,
+        body,
+        effects,
+        flash,
+    ) in enemy_query.iter_mut()
+    {
+        let speed = effects.map_or(1.0, StatusEffects::speed);
+
+        charger.now += time.delta_seconds();
+
+        match charger.state {
+            ChargerState::Idle => {
+                velocity.0.x = (player_transform.translation.x
+                    - enemy_transform.translation.x)
+                    .signum()
+                    * charger.speed
+                    * speed;
+
+                if charger.now >= charger.idle {
+                    charger.state = ChargerState::Windup;
+                    charger.now = 0.0;
+                    charger.color = effects
+                        .and_then(StatusEffects::base_color)
+                        .or_else(|| flash.map(|flash| flash.color))
+                        .unwrap_or(sprite.color);
+                }
+            }
+            ChargerState::Windup => {
+                velocity.0.x = 0.0;
+
+                if charger.now >= charger.windup {
+                    charger.state = ChargerState::Dash;
+                    charger.now = 0.0;
+                    charger.target = player_transform.translation.x;
+                    charger.direction = (charger.target
+                        - enemy_transform.translation.x)
+                        .signum();
+                    sprite.color = charger.color;
+                } else {
+                    let m = charger.now % ENEMY_CHARGER_FLASH_FREQUENCY
+                        / ENEMY_CHARGER_FLASH_FREQUENCY;
+                    if m < 0.5 {
+                        sprite.color = Color::RED;
+                    } else {
+                        sprite.color = charger.color;
+                    }
+                }
+            }
+            ChargerState::Dash => {
+                velocity.0.x = charger.direction * charger.dash_speed * speed;
+
+                if charger.direction < 0.0 && body.left
+                    || charger.direction > 0.0 && body.right
+                {
+                    charger.state = ChargerState::Stunned;
+                    charger.now = 0.0;
+                    velocity.0.x = 0.0;
+                    sprite.color = Color::GRAY;
+                } else if charger.now >= charger.dash
+                    || (charger.target - enemy_transform.translation.x)
+                        * charger.direction
+                        <= 0.0
+                {
+                    charger.state = ChargerState::Recover;
+                    charger.now = 0.0;
+                    velocity.0.x = 0.0;
+                }
+            }
+            ChargerState::Recover => {
+                velocity.0.x = 0.0;
+
+                if charger.now >= charger.recover {
+                    charger.state = ChargerState::Idle;
+                    charger.now = 0.0;
+                }
+            }
+            ChargerState::Stunned => {
+                velocity.0.x = 0.0;
+
+                if charger.now >= charger.stun {
+                    charger.state = ChargerState::Idle;
+                    charger.now = 0.0;
+                    sprite.color = charger.color;
+                }
+            }
+        }
+    }
+}
+
+pub fn splitter(
+    player_query: Query<&Transform, With<Player>>,
+    mut enemy_query: Query<
+        (&mut Velocity, &Transform, &Splitter, Option<&StatusEffects>),
+        (Without<Dead>, Without<Knockback>),
+    >,
+) {
+    let player_transform = if let Ok(result) = player_query.get_single() {
+        result
+    } else {
+        return;
+    };
+
+    for (mut velocity, enemy_transform, splitter, effects) in
+        enemy_query.iter_mut()
+    {
+        velocity.0.x = (player_transform.translation.x
+            - enemy_transform.translation.x)
+            .signum()
+            * splitter.speed
+            * effects.map_or(1.0, StatusEffects::speed);
+    }
+}
+
+pub fn split(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    query: Query<(&Transform, &Sprite, &Splitter), Added<Dead>>,
+) {
+    for (transform, sprite, splitter) in query.iter() {
+        if splitter.generation == 0 {
+            continue;
+        }
+
+        let size = sprite.custom_size.unwrap() * ENEMY_SPLITTER_SCALE;
+
+        for index in 0..splitter.count {
+            let direction = if index % 2 == 0 { -1.0 } else { 1.0 };
+
+            commands
+                .spawn_bundle(SpriteBundle {
+                    sprite: Sprite {
+                        color: ENEMY_SPLITTER_COLOR,
+                        custom_size: Some(size),
+                        ..Default::default()
+                    },
+                    transform: Transform::from_xyz(
+                        transform.translation.x + direction * size.x / 2.0,
+                        transform.translation.y,
+                        transform.translation.z,
+                    ),
+                    texture: asset_server.load("enemy/walker/move.png"),
+                    ..Default::default()
+                })
+                .insert(GameEntity)
+                .insert(Flippable)
+                .insert(Acceleration(Vec2::new(0.0, GRAVITY)))
+                .insert(Velocity(Vec2::new(0.0, 1024.0)))
+                .insert(Body::default())
+                .insert(Enemy)
+                .insert(Health::new(splitter.generation))
+                .insert(Stompable::default())
+                .insert(Splitter {
+                    speed: splitter.speed * ENEMY_SPLITTER_SPEEDUP,
+                    generation: splitter.generation - 1,
+                    count: splitter.count,
+                });
+        }
+    }
+}
+
+pub fn nest(
+    mut commands: Commands,
+    time: Res<Time>,
+    asset_server: Res<AssetServer>,
+    mut query: Query<
+        (Entity, &mut Nest, &Transform),
+        (Without<Spawning>, Without<Dead>),
+    >,
+) {
+    for (entity, mut nest, transform) in query.iter_mut() {
+        nest.now += time.delta_seconds();
+
+        while nest.now >= nest.max && nest.budget > 0 {
+            let direction = if nest.budget % 2 == 0 { -1.0 } else { 1.0 };
+
+            commands
+                .spawn_bundle(SpriteBundle {
+                    sprite: Sprite {
+                        color: Color::rgba(1.0, 1.0, 1.0, 0.0),
+                        custom_size: Some(Vec2::new(ENEMY_SIZE, ENEMY_SIZE)),
+                        ..Default::default()
+                    },
+                    transform: Transform::from_xyz(
+                        transform.translation.x
+                            + direction * (ENEMY_NEST_SIZE + ENEMY_SIZE) / 2.0,
+                        transform.translation.y,
+                        GAME_LAYER,
+                    ),
+                    texture: asset_server.load("enemy/walker/move.png"),
+                    ..Default::default()
+                })
+                .insert(GameEntity)
+                .insert(Spawning { now: 0.0, max: 1.0 })
+                .insert(Enemy)
+                .insert(Health::new(1))
+                .insert(Stompable::default())
+                .insert(Walker { acceleration: 768.0 })
+                .insert(Minion(entity));
+
+            nest.budget -= 1;
+            nest.now -= nest.max;
+        }
+    }
+}
+
+pub fn nest_death(
+    mut died: EventWriter<Died>,
+    nest_query: Query<Entity, (With<Nest>, Added<Dead>)>,
+    mut minion_query: Query<(Entity, &Minion, &mut Health), Without<Dead>>,
+) {
+    for nest_entity in nest_query.iter() {
+        for (minion_entity, Minion(nest), mut health) in minion_query.iter_mut()
+        {
+            let current = health.current;
+
+            if *nest == nest_entity
+                && health.damage(current, DamageType::Environment) > 0
+            {
+                died.send(Died {
+                    entity: minion_entity,
+                    kind: DamageType::Environment,
+                });
+            }
+        }
+    }
+}
+
+pub fn died(
+    mut commands: Commands,
+    mut score: ResMut<Score>,
+    mut events: EventReader<Died>,
+    audio: Res<Audio>,
+    asset_server: Res<AssetServer>,
+    query: Query<(), (With<Enemy>, Without<Dead>)>,
+) {
+    for event in events.iter() {
+        if query.get(event.entity).is_err() {
+            continue;
+        }
+
+        if event.kind != DamageType::Environment {
+            audio.play(asset_server.load("kill.wav"));
+            score.0 += 1;
+        }
+
+        commands.entity(event.entity).insert(Dead);
+    }
+}
+
+pub fn death(
+    mut commands: Commands,
+    mut query: Query<(Entity, Option<&mut Acceleration>), Added<Dead>>,
+) {
+    for (entity, acceleration) in query.iter_mut() {
+        if let Some(mut acceleration) = acceleration {
+            acceleration.0.x = 0.0;
+        }
+
+        commands
+            .entity(entity)
+            .remove::<Spawning>()
+            .insert(Dying { now: 0.0, max: ENEMY_DYING_TIME });
+    }
+}
+
+pub fn dying(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut Dying, &mut Transform, &mut Sprite)>,
+) {
+    for (entity, mut dying, mut transform, mut sprite) in query.iter_mut() {
+        dying.now += time.delta_seconds();
+
+        if dying.now >= dying.max {
+            commands.entity(entity).despawn();
+        } else {
+            let direction = if sprite.flip_x { 1.0 } else { -1.0 };
+
+            transform.rotate(Quat::from_rotation_z(
+                direction * ENEMY_DYING_SPIN * time.delta_seconds(),
+            ));
+            sprite.color.set_a(1.0 - dying.now / dying.max);
+        }
+    }
+}
+
+pub fn damage(
+    mut score: ResMut<Score>,
+    mut died: EventWriter<Died>,
+    audio: Res<Audio>,
+    asset_server: Res<AssetServer>,
+    mut player_query: Query<(
+        Entity,
+        &mut Player,
+        &mut Health,
+        &mut Velocity,
+        &Transform,
+        &Sprite,
+    )>,
+    mut enemy_query: Query<
+        (Entity, &mut Health, &Transform, &Sprite, Option<&mut Stompable>),
+        (With<Enemy>, Without<Spawning>, Without<Dead>, Without<Player>),
+    >,
+) {
+    let (
+        player_entity,
+        mut player,
+        mut player_health,
+        mut player_velocity,
+        player_transform,
+        player_sprite,
+    ) = if let Ok(result) = player_query.get_single_mut() {
+        result
+    } else {
+        return;
+    };
+
+    for (entity, mut health, enemy_transform, enemy_sprite, mut stompable) in
+        enemy_query.iter_mut()
+    {
+        if let Some(stompable) = stompable.as_mut() {
+            stompable.now = (stompable.now + TIME_STEP).min(stompable.max);
+        }
+
+        let collision = collide_aabb::collide(
+            player_transform.translation,
+            player_sprite.custom_size.unwrap(),
+            enemy_transform.translation,
+            enemy_sprite.custom_size.unwrap(),
+        );
+
+        match (collision, stompable) {
+            (Some(Collision::Top), Some(mut stompable))
+                if player_velocity.0.y < 0.0 =>
+            {
+                player_velocity.0.y = ENEMY_STOMP_BOUNCE;
+                player.jumping = false;
+
+                if stompable.now < stompable.max {
+                    break;
+                }
+
+                stompable.now = 0.0;
+
+                if health.damage(stompable.damage, DamageType::Contact) > 0 {
+                    if health.current > 0 {
+                        audio.play(asset_server.load("hit.wav"));
+                    } else {
+                        score.0 += ENEMY_STOMP_SCORE;
+                        died.send(Died { entity, kind: DamageType::Contact });
+                    }
+                }
+
+                break;
+            }
+            (Some(_), _) => {
+                if player_health.damage(1, DamageType::Contact) > 0 {
+                    audio.play(asset_server.load("damage.wav"));
+
+                    if player_health.current == 0 {
+                        died.send(Died {
+                            entity: player_entity,
+                            kind: DamageType::Contact,
+                        });
+                    }
+                }
+            }
+            (None, _) => {}
+        }
+    }
+}
+
+pub fn bullet(
+    mut commands: Commands,
+    mut died: EventWriter<Died>,
+    audio: Res<Audio>,
+    asset_server: Res<AssetServer>,
+    mut player_query: Query<
+        (
+            Entity,
+            &mut Health,
+            &Transform,
+            &Sprite,
+            &Melee,
+            Option<&mut StatusEffects>,
+        ),
+        With<Player>,
+    >,
+    mut bullet_query: Query<
+        (Entity, &Transform, &mut Sprite, &mut Velocity, &Bullet),
+        Without<Player>,
+    >,
+) {
+    let (
+        player_entity,
+        mut health,
+        player_transform,
+        player_sprite,
+        melee,
+        mut effects,
+    ) = if let Ok(result) = player_query.get_single_mut() {
+        result
+    } else {
+        return;
+    };
+
+    for (entity, bullet_transform, mut bullet_sprite, mut velocity, bullet) in
+        bullet_query.iter_mut()
+    {
+        if collide_aabb::collide(
+            player_transform.translation,
+            player_sprite.custom_size.unwrap(),
+            bullet_transform.translation,
+            bullet_sprite.custom_size.unwrap(),
+        )
+        .is_some()
+        {
+            if melee.parrying() {
+                melee::parry(
+                    &mut commands,
+                    entity,
+                    &mut velocity,
+                    &mut bullet_sprite,
+                );
+            } else {
+                commands.entity(entity).despawn();
+
+                if health.damage(1, DamageType::Projectile) > 0 {
+                    audio.play(asset_server.load("damage.wav"));
+                    status::inflict(
+                        &mut commands,
+                        player_entity,
+                        effects.as_deref_mut(),
+                        &bullet.modifiers,
+                        DamageType::Projectile,
+                    );
+
+                    if health.current == 0 {
+                        died.send(Died {
+                            entity: player_entity,
+                            kind: DamageType::Projectile,
+                        });
+                    }
+                }
+            }
+        }
+    }
+}
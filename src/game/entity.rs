@@ -2,6 +2,7 @@ use bevy::prelude::*;
 
 pub const BACKGROUND_LAYER: f32 = 0.0;
 pub const GAME_LAYER: f32 = 1.0;
+pub const OVERLAY_LAYER: f32 = 2.0;
 
 #[derive(Component)]
 pub struct GameEntity;
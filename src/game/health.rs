@@ -0,0 +1,97 @@
+use bevy::prelude::*;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum DamageType {
+    Contact,
+    Projectile,
+    Explosion,
+    Environment,
+}
+
+#[derive(Clone, Copy, Default)]
+pub struct Resistance {
+    pub contact: f32,
+    pub projectile: f32,
+    pub explosion: f32,
+    pub environment: f32,
+}
+
+impl Resistance {
+    fn get(&self, kind: DamageType) -> f32 {
+        match kind {
+            DamageType::Contact => self.contact,
+            DamageType::Projectile => self.projectile,
+            DamageType::Explosion => self.explosion,
+            DamageType::Environment => self.environment,
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct Health {
+    pub current: u32,
+    pub max: u32,
+    pub resistance: Resistance,
+    pub shield: u32,
+    pub now: f32,
+    pub invincibility: f32,
+}
+
+impl Health {
+    pub fn new(max: u32) -> Self {
+        Health {
+            current: max,
+            max,
+            resistance: Resistance::default(),
+            shield: 0,
+            now: 0.0,
+            invincibility: 0.0,
+        }
+    }
+
+    pub fn invincible(&self) -> bool {
+        self.now < self.invincibility
+    }
+
+    pub fn damage(&mut self, amount: u32, kind: DamageType) -> u32 {
+        if self.current == 0 || self.invincible() {
+            return 0;
+        }
+
+        let amount = ((amount as f32 * (1.0 - self.resistance.get(kind)))
+            .round()
+            .max(0.0) as u32)
+            .min(self.current);
+
+        if amount > 0 && self.shield > 0 {
+            self.shield -= 1;
+            self.now = 0.0;
+            return 0;
+        }
+
+        if amount > 0 {
+            self.current -= amount;
+            self.now = 0.0;
+        }
+
+        amount
+    }
+
+    pub fn heal(&mut self, amount: u32) {
+        self.current = (self.current + amount).min(self.max);
+    }
+}
+
+pub struct Died {
+    pub entity: Entity,
+    pub kind: DamageType,
+}
+
+pub fn invincibility(time: Res<Time>, mut query: Query<&mut Health>) {
+    for mut health in query.iter_mut() {
+        if health.invincible() {
+            health.now =
+                (health.now + time.delta_seconds()).min(health.invincibility);
+        }
+    }
+}
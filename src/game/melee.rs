@@ -0,0 +1,144 @@
+use bevy::prelude::*;
+use bevy_kira_audio::Audio;
+
+use super::{
+    enemy,
+    entity::GameEntity,
+    health::DamageType,
+    physics::{Lifetime, Velocity},
+    player::{Bullet, Player},
+    weapon::Modifiers,
+};
+
+const MELEE_WIDTH: f32 = 160.0;
+const MELEE_HEIGHT: f32 = 192.0;
+const MELEE_OFFSET: f32 = 144.0;
+const MELEE_COLOR: Color = Color::rgba(1.0, 1.0, 1.0, 0.5);
+const MELEE_DAMAGE: u32 = 2;
+const MELEE_KNOCKBACK: f32 = 768.0;
+const MELEE_HIT_STOP: u32 = 3;
+const MELEE_FLASH: f32 = 1.5;
+const MELEE_TIME: f32 = 0.12;
+
+const PARRY_SPEED: f32 = 1.5;
+const PARRY_DAMAGE: u32 = 2;
+const PARRY_COLOR: Color = Color::rgb(0.4, 1.0, 1.0);
+
+#[derive(Component)]
+pub struct Melee {
+    pub now: f32,
+    pub max: f32,
+    parry_now: f32,
+    parry_max: f32,
+}
+
+impl Default for Melee {
+    fn default() -> Self {
+        Melee { now: 0.4, max: 0.4, parry_now: 0.15, parry_max: 0.15 }
+    }
+}
+
+impl Melee {
+    pub fn parrying(&self) -> bool {
+        self.parry_now < self.parry_max
+    }
+}
+
+#[derive(Component)]
+pub struct Swing {
+    offset: Vec2,
+}
+
+pub fn parry(
+    commands: &mut Commands,
+    entity: Entity,
+    velocity: &mut Velocity,
+    sprite: &mut Sprite,
+) {
+    velocity.0 = -velocity.0 * PARRY_SPEED;
+    sprite.color = PARRY_COLOR;
+
+    commands.entity(entity).remove::<enemy::Bullet>().insert(Bullet::new(
+        PARRY_DAMAGE,
+        DamageType::Projectile,
+        Modifiers::default(),
+        MELEE_KNOCKBACK / 2.0,
+        MELEE_HIT_STOP,
+        MELEE_FLASH,
+    ));
+}
+
+pub fn swing(
+    mut commands: Commands,
+    mouse_input: Res<Input<MouseButton>>,
+    keyboard_input: Res<Input<KeyCode>>,
+    audio: Res<Audio>,
+    asset_server: Res<AssetServer>,
+    time: Res<Time>,
+    mut query: Query<(&Transform, &Player, &mut Melee)>,
+) {
+    let (transform, player, mut melee) =
+        if let Ok(result) = query.get_single_mut() {
+            result
+        } else {
+            return;
+        };
+
+    melee.now = (melee.now + time.delta_seconds()).min(melee.max);
+    melee.parry_now =
+        (melee.parry_now + time.delta_seconds()).min(melee.parry_max);
+
+    if melee.now >= melee.max
+        && (mouse_input.just_pressed(MouseButton::Middle)
+            || keyboard_input.just_pressed(KeyCode::C))
+    {
+        audio.play(asset_server.load("jump.wav"));
+        melee.now = 0.0;
+        melee.parry_now = 0.0;
+
+        let offset = Vec2::new(player.direction * MELEE_OFFSET, 0.0);
+
+        commands
+            .spawn_bundle(SpriteBundle {
+                sprite: Sprite {
+                    color: MELEE_COLOR,
+                    custom_size: Some(Vec2::new(MELEE_WIDTH, MELEE_HEIGHT)),
+                    flip_x: player.direction < 0.0,
+                    ..Default::default()
+                },
+                transform: Transform::from_translation(
+                    transform.translation + offset.extend(0.0),
+                ),
+                texture: asset_server.load("bullet.png"),
+                ..Default::default()
+            })
+            .insert(GameEntity)
+            .insert(Velocity(Vec2::new(player.direction, 0.0)))
+            .insert(Lifetime { now: 0.0, max: MELEE_TIME })
+            .insert(Swing { offset })
+            .insert(Bullet::new(
+                MELEE_DAMAGE,
+                DamageType::Contact,
+                Modifiers { pierce: u32::MAX, ..Default::default() },
+                MELEE_KNOCKBACK,
+                MELEE_HIT_STOP,
+                MELEE_FLASH,
+            ));
+    }
+}
+
+pub fn follow(
+    player_query: Query<&Transform, With<Player>>,
+    mut swing_query: Query<(&mut Transform, &Swing), Without<Player>>,
+) {
+    let player_transform = if let Ok(result) = player_query.get_single() {
+        result
+    } else {
+        return;
+    };
+
+    for (mut transform, swing) in swing_query.iter_mut() {
+        transform.translation =
+            player_transform.translation + swing.offset.extend(0.0);
+    }
+}
@@ -1,49 +1,93 @@
 use bevy::{core::FixedTimestep, ecs::schedule::ShouldRun, prelude::*};
-use physics::TIME_STEP;
+use health::Died;
+use physics::{HitStop, TIME_STEP};
+use settings::Settings;
 
 use crate::GameState;
 
 mod animation;
 mod background;
+mod boss;
+mod elite;
 mod enemy;
 mod entity;
+mod health;
+mod melee;
+mod pattern;
 mod physics;
+mod pickup;
 mod player;
+mod settings;
+mod status;
 mod ui;
+mod upgrade;
+mod weapon;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, SystemLabel)]
 enum GameSystem {
     Acceleration,
     Velocity,
+    Walls,
+    Aim,
+    Damage,
+    Death,
 }
 
 pub struct GamePlugin;
 
 impl Plugin for GamePlugin {
     fn build(&self, app: &mut App) {
+        app.init_resource::<Settings>()
+            .init_resource::<HitStop>()
+            .add_event::<Died>();
+
         app.add_system_set(
             SystemSet::on_enter(GameState::Game)
                 .with_system(background::init)
+                .with_system(boss::init)
+                .with_system(elite::init)
                 .with_system(enemy::init)
                 .with_system(player::init)
+                .with_system(weapon::init)
+                .with_system(upgrade::init)
                 .with_system(ui::init),
         )
         .add_system_set(build_on_in_stack_update_system_set(
-            SystemSet::on_update(GameState::Game).with_system(enemy::prespawn),
+            SystemSet::on_update(GameState::Game)
+                .with_system(enemy::prespawn)
+                .with_system(boss::schedule)
+                .with_system(elite::promote)
+                .with_system(upgrade::check.after(GameSystem::Damage)),
         ))
         .add_system_set(build_on_in_stack_update_system_set(
-            SystemSet::on_inactive_update(GameState::Game),
+            SystemSet::new().with_run_criteria(
+                |state: Res<State<GameState>>| {
+                    if *state.current() != GameState::Upgrade
+                        && state.inactives().contains(&GameState::Game)
+                    {
+                        ShouldRun::Yes
+                    } else {
+                        ShouldRun::No
+                    }
+                },
+            ),
         ))
         .add_system_set(
             SystemSet::new()
                 .with_run_criteria(FixedTimestep::step(TIME_STEP as f64).chain(
-                    |In(input), state: Res<State<GameState>>| {
-                        if *state.current() == GameState::Game
-                            || state.inactives().contains(&GameState::Game)
+                    |In(input),
+                     state: Res<State<GameState>>,
+                     mut hit_stop: ResMut<HitStop>| {
+                        if *state.current() == GameState::Upgrade
+                            || *state.current() != GameState::Game
+                                && !state.inactives().contains(&GameState::Game)
                         {
-                            input
-                        } else {
                             ShouldRun::No
+                        } else if input != ShouldRun::No && hit_stop.ticks > 0 {
+                            hit_stop.ticks -= 1;
+                            ShouldRun::No
+                        } else {
+                            input
                         }
                     },
                 ))
@@ -52,15 +96,43 @@ impl Plugin for GamePlugin {
                         .label(GameSystem::Acceleration)
                         .before(GameSystem::Velocity),
                 )
+                .with_system(player::homing.before(GameSystem::Acceleration))
                 .with_system(physics::velocity.label(GameSystem::Velocity))
-                .with_system(physics::walls.after(GameSystem::Velocity))
-                .with_system(player::bullet.after(GameSystem::Velocity))
-                .with_system(enemy::bullet.after(GameSystem::Velocity))
-                .with_system(enemy::damage.after(GameSystem::Velocity))
+                .with_system(
+                    physics::walls
+                        .label(GameSystem::Walls)
+                        .after(GameSystem::Velocity),
+                )
+                .with_system(player::bounce.after(GameSystem::Walls))
+                .with_system(
+                    player::bullet
+                        .label(GameSystem::Damage)
+                        .after(GameSystem::Velocity),
+                )
+                .with_system(
+                    enemy::bullet
+                        .label(GameSystem::Damage)
+                        .after(GameSystem::Velocity),
+                )
+                .with_system(
+                    enemy::damage
+                        .label(GameSystem::Damage)
+                        .after(GameSystem::Velocity),
+                )
                 .with_system(physics::cleanup.after(GameSystem::Velocity)),
         )
         .add_system_set(
             SystemSet::on_exit(GameState::Game).with_system(entity::deinit),
+        )
+        .add_system_set(
+            SystemSet::on_enter(GameState::Upgrade).with_system(upgrade::menu),
+        )
+        .add_system_set(
+            SystemSet::on_update(GameState::Upgrade)
+                .with_system(upgrade::select),
+        )
+        .add_system_set(
+            SystemSet::on_exit(GameState::Upgrade).with_system(upgrade::deinit),
         );
     }
 }
@@ -73,12 +145,60 @@ fn build_on_in_stack_update_system_set(system_set: SystemSet) -> SystemSet {
         .with_system(enemy::walker.before(GameSystem::Acceleration))
         .with_system(enemy::shooter.before(GameSystem::Velocity))
         .with_system(enemy::jumper.before(GameSystem::Velocity))
+        .with_system(enemy::charger.before(GameSystem::Velocity))
+        .with_system(enemy::splitter.before(GameSystem::Velocity))
+        .with_system(enemy::behavior::brain.before(GameSystem::Velocity))
+        .with_system(enemy::nest)
+        .with_system(enemy::split.before(GameSystem::Death))
+        .with_system(
+            enemy::nest_death
+                .label(GameSystem::Damage)
+                .before(GameSystem::Death),
+        )
+        .with_system(enemy::death.label(GameSystem::Death))
+        .with_system(enemy::dying.after(GameSystem::Death))
+        .with_system(boss::movement.before(GameSystem::Velocity))
+        .with_system(boss::phase)
+        .with_system(pattern::gun.after(GameSystem::Velocity))
+        .with_system(physics::lifetime)
+        .with_system(physics::knockback)
+        .with_system(animation::flash)
+        .with_system(boss::death.before(GameSystem::Death))
+        .with_system(elite::haste)
+        .with_system(elite::regenerate)
+        .with_system(elite::death.before(GameSystem::Death))
         .with_system(player::movement.system().before(GameSystem::Velocity))
         .with_system(player::animation.after(GameSystem::Velocity))
         .with_system(animation::flip.after(GameSystem::Velocity))
-        .with_system(player::shoot.after(GameSystem::Velocity))
-        .with_system(player::damage.after(GameSystem::Velocity))
+        .with_system(weapon::switch.before(GameSystem::Velocity))
+        .with_system(settings::toggle.before(GameSystem::Aim))
+        .with_system(
+            player::aim.label(GameSystem::Aim).after(GameSystem::Velocity),
+        )
+        .with_system(player::shoot.after(GameSystem::Aim))
+        .with_system(player::died)
+        .with_system(enemy::died.after(GameSystem::Damage))
+        .with_system(health::invincibility)
+        .with_system(status::tick.label(GameSystem::Damage))
+        .with_system(status::tint)
+        .with_system(pickup::drop.after(GameSystem::Damage))
+        .with_system(pickup::expire)
+        .with_system(pickup::collect.after(GameSystem::Velocity))
+        .with_system(pickup::boost)
         .with_system(player::invincibility)
+        .with_system(player::afterimage.after(GameSystem::Velocity))
+        .with_system(ui::dash)
         .with_system(ui::health)
+        .with_system(ui::shield)
         .with_system(ui::score)
+        .with_system(melee::swing)
+        .with_system(melee::follow)
+        .with_system(ui::weapon)
+        .with_system(ui::supply)
+        .with_system(weapon::supply.before(GameSystem::Aim))
+        .with_system(weapon::charge.after(GameSystem::Velocity))
+        .with_system(ui::boss_health)
+        .with_system(ui::damage)
+        .with_system(ui::enemy_health)
+        .with_system(ui::damage_number)
 }
@@ -0,0 +1,188 @@
+use std::f32::consts::PI;
+
+use bevy::prelude::*;
+
+use super::{
+    enemy::{self, Dead, Spawning},
+    player::Player,
+    weapon::Modifiers,
+};
+
+#[derive(Clone, Copy)]
+pub enum Shape {
+    Forward,
+    Aimed,
+    Spread { count: u32, angle: f32 },
+    Ring { count: u32 },
+    Spiral { count: u32, step: f32 },
+    Burst { count: u32, interval: f32 },
+}
+
+#[derive(Clone, Copy)]
+pub struct Pattern {
+    pub shape: Shape,
+    pub speed: f32,
+    pub lifetime: Option<f32>,
+}
+
+#[derive(Component)]
+pub struct Gun {
+    pub pattern: Pattern,
+    pub modifiers: Modifiers,
+    pub active: bool,
+    pub now: f32,
+    pub max: f32,
+    angle: f32,
+    shots: u32,
+    shot_now: f32,
+}
+
+impl Gun {
+    pub fn new(pattern: Pattern, max: f32) -> Self {
+        Gun {
+            pattern,
+            modifiers: Modifiers::default(),
+            active: true,
+            now: 0.0,
+            max,
+            angle: 0.0,
+            shots: 0,
+            shot_now: 0.0,
+        }
+    }
+
+    pub fn with_modifiers(mut self, modifiers: Modifiers) -> Self {
+        self.modifiers = modifiers;
+        self
+    }
+
+    fn fire(
+        &mut self,
+        commands: &mut Commands,
+        asset_server: &AssetServer,
+        translation: Vec3,
+        facing: f32,
+        aim: f32,
+    ) {
+        let Pattern { shape, speed, lifetime } = self.pattern;
+        let modifiers = self.modifiers;
+
+        let mut spawn = |angle| {
+            enemy::spawn_bullet(
+                commands,
+                asset_server,
+                translation,
+                angle,
+                speed,
+                lifetime,
+                modifiers,
+            )
+        };
+
+        match shape {
+            Shape::Forward => spawn(if facing < 0.0 { PI } else { 0.0 }),
+            Shape::Aimed => spawn(aim),
+            Shape::Spread { count, angle } => {
+                if count > 1 {
+                    for index in 0..count {
+                        spawn(
+                            aim - angle / 2.0
+                                + angle * index as f32 / (count - 1) as f32,
+                        );
+                    }
+                } else {
+                    spawn(aim);
+                }
+            }
+            Shape::Ring { count } => {
+                for index in 0..count {
+                    spawn(index as f32 * 2.0 * PI / count as f32);
+                }
+            }
+            Shape::Spiral { count, step } => {
+                for index in 0..count {
+                    spawn(self.angle + index as f32 * 2.0 * PI / count as f32);
+                }
+
+                self.angle = (self.angle + step) % (2.0 * PI);
+            }
+            Shape::Burst { count, .. } => {
+                spawn(aim);
+
+                if self.shots == 0 {
+                    self.shots = count.max(1) - 1;
+                    self.shot_now = 0.0;
+                } else {
+                    self.shots -= 1;
+                }
+            }
+        }
+    }
+}
+
+pub fn gun(
+    mut commands: Commands,
+    time: Res<Time>,
+    asset_server: Res<AssetServer>,
+    player_query: Query<&Transform, With<Player>>,
+    mut enemy_query: Query<
+        (&mut Gun, &Transform, &Sprite),
+        (Without<Spawning>, Without<Dead>),
+    >,
+) {
+    let player_transform = player_query.get_single().ok();
+
+    for (mut gun, enemy_transform, sprite) in enemy_query.iter_mut() {
+        let facing = if sprite.flip_x { -1.0 } else { 1.0 };
+        let aim = if let Some(player_transform) = player_transform {
+            (player_transform.translation.y - enemy_transform.translation.y)
+                .atan2(
+                    player_transform.translation.x
+                        - enemy_transform.translation.x,
+                )
+        } else if facing < 0.0 {
+            PI
+        } else {
+            0.0
+        };
+
+        if gun.shots > 0 {
+            if let Shape::Burst { interval, .. } = gun.pattern.shape {
+                gun.shot_now += time.delta_seconds();
+
+                while gun.shots > 0 && gun.shot_now >= interval {
+                    gun.shot_now -= interval;
+                    gun.fire(
+                        &mut commands,
+                        &asset_server,
+                        enemy_transform.translation,
+                        facing,
+                        aim,
+                    );
+                }
+            } else {
+                gun.shots = 0;
+            }
+
+            continue;
+        }
+
+        if !gun.active {
+            gun.now = (gun.now + time.delta_seconds()).min(gun.max);
+            continue;
+        }
+
+        gun.now += time.delta_seconds();
+
+        while gun.shots == 0 && gun.now >= gun.max {
+            gun.now -= gun.max;
+            gun.fire(
+                &mut commands,
+                &asset_server,
+                enemy_transform.translation,
+                facing,
+                aim,
+            );
+        }
+    }
+}
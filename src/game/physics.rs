@@ -14,6 +14,23 @@ pub struct Velocity(pub Vec2);
 #[derive(Component)]
 pub struct Cleanup;
 
+#[derive(Component)]
+pub struct Knockback {
+    pub now: f32,
+    pub max: f32,
+}
+
+#[derive(Default)]
+pub struct HitStop {
+    pub ticks: u32,
+}
+
+#[derive(Component)]
+pub struct Lifetime {
+    pub now: f32,
+    pub max: f32,
+}
+
 #[derive(Default, Component)]
 pub struct Body {
     pub left: bool,
@@ -67,6 +84,34 @@ pub fn cleanup(
     }
 }
 
+pub fn lifetime(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut Lifetime)>,
+) {
+    for (entity, mut lifetime) in query.iter_mut() {
+        lifetime.now += time.delta_seconds();
+
+        if lifetime.now >= lifetime.max {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+pub fn knockback(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut Knockback)>,
+) {
+    for (entity, mut knockback) in query.iter_mut() {
+        knockback.now += time.delta_seconds();
+
+        if knockback.now >= knockback.max {
+            commands.entity(entity).remove::<Knockback>();
+        }
+    }
+}
+
 pub fn walls(
     mut queries: QuerySet<(
         QueryState<(&Transform, &OrthographicProjection), With<GameCamera>>,
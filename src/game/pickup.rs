@@ -0,0 +1,235 @@
+use std::f32::consts::PI;
+
+use bevy::{prelude::*, sprite::collide_aabb};
+use bevy_kira_audio::Audio;
+use rand::Rng;
+
+use super::{
+    enemy::{Enemy, Score},
+    entity::{GameEntity, GAME_LAYER},
+    health::{DamageType, Died, Health},
+    physics::{Acceleration, Body, Velocity, GRAVITY},
+    player::Player,
+    weapon::Modifiers,
+};
+
+const PICKUP_SIZE: f32 = 48.0;
+const PICKUP_DROP_CHANCE: f32 = 0.35;
+const PICKUP_DROP_SPEED: f32 = 1024.0;
+const PICKUP_TIME: f32 = 8.0;
+const PICKUP_BLINK_TIME: f32 = 2.0;
+const PICKUP_BLINK_FREQUENCY: f32 = 1.0 / 8.0;
+const PICKUP_HEALTH: u32 = 1;
+const PICKUP_GEM_SCORE: u32 = 5;
+pub const PICKUP_SHIELD_MAX: u32 = 2;
+pub const PICKUP_SHIELD_COLOR: Color = Color::rgb(0.5, 0.6, 1.0);
+const PICKUP_BOOST_TIME: f32 = 10.0;
+const PICKUP_DROP_TABLE: [(PickupKind, u32); 4] = [
+    (PickupKind::Health, 2),
+    (PickupKind::Boost, 2),
+    (PickupKind::Gem, 5),
+    (PickupKind::Shield, 1),
+];
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum PickupKind {
+    Health,
+    Boost,
+    Gem,
+    Shield,
+}
+
+impl PickupKind {
+    fn color(self) -> Color {
+        match self {
+            PickupKind::Health => Color::rgb(1.0, 0.3, 0.3),
+            PickupKind::Boost => Color::rgb(1.0, 0.8, 0.2),
+            PickupKind::Gem => Color::rgb(0.4, 1.0, 1.0),
+            PickupKind::Shield => PICKUP_SHIELD_COLOR,
+        }
+    }
+
+    fn roll() -> Option<Self> {
+        let mut rng = rand::thread_rng();
+
+        if !rng.gen_bool(PICKUP_DROP_CHANCE as f64) {
+            return None;
+        }
+
+        let total: u32 =
+            PICKUP_DROP_TABLE.iter().map(|(_, weight)| weight).sum();
+        let mut roll = rng.gen_range(0..total);
+
+        for (kind, weight) in PICKUP_DROP_TABLE {
+            if roll < weight {
+                return Some(kind);
+            }
+
+            roll -= weight;
+        }
+
+        None
+    }
+}
+
+#[derive(Component)]
+pub struct Pickup {
+    kind: PickupKind,
+    now: f32,
+    max: f32,
+}
+
+#[derive(Component)]
+pub struct Boost {
+    pub modifiers: Modifiers,
+    now: f32,
+    max: f32,
+}
+
+pub fn drop(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut events: EventReader<Died>,
+    query: Query<&Transform, With<Enemy>>,
+) {
+    for event in events.iter() {
+        if event.kind == DamageType::Environment {
+            continue;
+        }
+
+        let transform = if let Ok(result) = query.get(event.entity) {
+            result
+        } else {
+            continue;
+        };
+
+        let kind = if let Some(result) = PickupKind::roll() {
+            result
+        } else {
+            continue;
+        };
+
+        let angle = rand::thread_rng().gen_range(PI / 3.0..PI * 2.0 / 3.0);
+
+        commands
+            .spawn_bundle(SpriteBundle {
+                sprite: Sprite {
+                    color: kind.color(),
+                    custom_size: Some(Vec2::new(PICKUP_SIZE, PICKUP_SIZE)),
+                    ..Default::default()
+                },
+                transform: Transform::from_xyz(
+                    transform.translation.x,
+                    transform.translation.y,
+                    GAME_LAYER,
+                ),
+                texture: asset_server.load("bullet.png"),
+                ..Default::default()
+            })
+            .insert(GameEntity)
+            .insert(Acceleration(Vec2::new(0.0, GRAVITY)))
+            .insert(Velocity(
+                Vec2::new(angle.cos(), angle.sin()) * PICKUP_DROP_SPEED,
+            ))
+            .insert(Body::default())
+            .insert(Pickup { kind, now: 0.0, max: PICKUP_TIME });
+    }
+}
+
+pub fn expire(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(
+        Entity,
+        &mut Pickup,
+        &mut Velocity,
+        &mut Visibility,
+        &Body,
+    )>,
+) {
+    for (entity, mut pickup, mut velocity, mut visibility, body) in
+        query.iter_mut()
+    {
+        pickup.now += time.delta_seconds();
+
+        if body.bottom {
+            velocity.0.x = 0.0;
+        }
+
+        if pickup.now >= pickup.max {
+            commands.entity(entity).despawn();
+        } else if pickup.max - pickup.now < PICKUP_BLINK_TIME {
+            visibility.is_visible = pickup.now % PICKUP_BLINK_FREQUENCY
+                / PICKUP_BLINK_FREQUENCY
+                < 0.5;
+        }
+    }
+}
+
+pub fn collect(
+    mut commands: Commands,
+    mut score: ResMut<Score>,
+    audio: Res<Audio>,
+    asset_server: Res<AssetServer>,
+    mut player_query: Query<
+        (Entity, &mut Health, &Transform, &Sprite),
+        With<Player>,
+    >,
+    pickup_query: Query<(Entity, &Pickup, &Transform, &Sprite)>,
+) {
+    let (player_entity, mut health, player_transform, player_sprite) =
+        if let Ok(result) = player_query.get_single_mut() {
+            result
+        } else {
+            return;
+        };
+
+    for (entity, pickup, transform, sprite) in pickup_query.iter() {
+        if collide_aabb::collide(
+            player_transform.translation,
+            player_sprite.custom_size.unwrap(),
+            transform.translation,
+            sprite.custom_size.unwrap(),
+        )
+        .is_none()
+        {
+            continue;
+        }
+
+        match pickup.kind {
+            PickupKind::Health => health.heal(PICKUP_HEALTH),
+            PickupKind::Boost => {
+                commands.entity(player_entity).insert(Boost {
+                    modifiers: Modifiers {
+                        pierce: 1,
+                        homing: PI / 2.0,
+                        ..Default::default()
+                    },
+                    now: 0.0,
+                    max: PICKUP_BOOST_TIME,
+                });
+            }
+            PickupKind::Gem => score.0 += PICKUP_GEM_SCORE,
+            PickupKind::Shield => {
+                health.shield = (health.shield + 1).min(PICKUP_SHIELD_MAX);
+            }
+        }
+
+        audio.play(asset_server.load("pickup.wav"));
+        commands.entity(entity).despawn();
+    }
+}
+
+pub fn boost(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut Boost)>,
+) {
+    for (entity, mut boost) in query.iter_mut() {
+        boost.now += time.delta_seconds();
+
+        if boost.now >= boost.max {
+            commands.entity(entity).remove::<Boost>();
+        }
+    }
+}
@@ -1,38 +1,124 @@
+use std::f32::consts::PI;
+
 use bevy::{prelude::*, sprite::collide_aabb};
 use bevy_kira_audio::Audio;
+use rand::Rng;
 
-use crate::GameState;
+use crate::{plugin::camera::GameCamera, GameState};
 
 use super::{
-    animation::{Animation, Animations, Flippable, LoadAnimation},
-    enemy::{Enemy, Score, Spawning},
-    entity::{GameEntity, GAME_LAYER},
-    physics::{Acceleration, Body, Cleanup, Velocity, GRAVITY},
+    animation::{Animation, Animations, Flash, Flippable, LoadAnimation},
+    elite::Shielded,
+    enemy::{Dead, Enemy, HitFeedback, Spawning},
+    entity::{GameEntity, GAME_LAYER, OVERLAY_LAYER},
+    health::{DamageType, Died, Health},
+    melee::Melee,
+    physics::{
+        Acceleration, Body, Cleanup, HitStop, Knockback, Lifetime, Velocity,
+        GRAVITY, TIME_STEP,
+    },
+    pickup::Boost,
+    settings::{AimMode, Settings},
+    status::{self, StatusEffects},
+    upgrade::PlayerStats,
+    weapon::{Modifiers, Weapon, Weapons},
 };
 
 pub const PLAYER_HEALTH_MAX: u32 = 3;
+pub const PLAYER_HEALTH_LIMIT: u32 = 6;
 
 const PLAYER_STAND_ANIMATION: usize = 0;
 const PLAYER_MOVE_ANIMATION: usize = 1;
 const PLAYER_SIZE: f32 = 128.0;
 const PLAYER_INVINCIBILITY: f32 = 3.0;
 const PLAYER_FLASH_FREQUENCY: f32 = 1.0 / 4.0;
+const PLAYER_JUMP: f32 = 2048.0;
+const PLAYER_WALL_SLIDE_SPEED: f32 = 384.0;
+const PLAYER_WALL_JUMP_SPEED: f32 = 1024.0;
+const PLAYER_WALL_JUMP_TIME: f32 = 0.2;
+const PLAYER_DASH_SPEED: f32 = 2560.0;
+const PLAYER_DASH_TIME: f32 = 0.15;
+const PLAYER_DASH_COOLDOWN: f32 = 1.0;
+const PLAYER_DASH_INVINCIBILITY: f32 = 0.3;
+const PLAYER_AFTERIMAGE_INTERVAL: f32 = 0.03;
+const PLAYER_AFTERIMAGE_TIME: f32 = 0.25;
+const PLAYER_EXTRA_SPREAD: f32 = PI / 24.0;
+const PLAYER_AFTERIMAGE_COLOR: Color = Color::rgba(0.5, 0.8, 1.0, 0.5);
+
+const CROSSHAIR_SIZE: f32 = 48.0;
+const CROSSHAIR_DISTANCE: f32 = 256.0;
+const CROSSHAIR_COLOR: Color = Color::rgba(1.0, 0.3, 0.3, 0.75);
+const STICK_DEADZONE: f32 = 0.25;
 
-const BULLET_SIZE: f32 = 32.0;
+const EXPLOSION_TIME: f32 = 0.15;
+const EXPLOSION_COLOR: Color = Color::rgba(1.0, 0.6, 0.2, 0.5);
+
+const ENEMY_DEATH_KNOCKBACK: f32 = 1024.0;
+const ENEMY_KNOCKBACK_TIME: f32 = 0.15;
+const ENEMY_FLASH_COLOR: Color = Color::rgb(8.0, 8.0, 8.0);
 
 #[derive(Component)]
 pub struct Player {
-    pub damage: u32,
-    pub health: u32,
     speed: f32,
     aim: Vec2,
-    direction: f32,
+    pub direction: f32,
+    pub coyote_time: f32,
+    pub jump_buffer: f32,
+    pub air_jumps: u32,
+    pub jumping: bool,
+    coyote_now: f32,
+    jump_buffer_now: f32,
+    air_jumps_left: u32,
+    wall_jump_now: f32,
+    pub dash_now: f32,
+    pub dash_max: f32,
+    dash_time_now: f32,
+    dash_direction: Vec2,
+    afterimage_now: f32,
+}
+
+#[derive(Component)]
+pub struct Crosshair;
+
+#[derive(Component)]
+pub struct Afterimage {
     now: f32,
     max: f32,
 }
 
 #[derive(Component)]
-pub struct Bullet;
+pub struct Bullet {
+    pub damage: u32,
+    pub kind: DamageType,
+    pub modifiers: Modifiers,
+    pub knockback: f32,
+    pub hit_stop: u32,
+    pub flash: f32,
+    hits: Vec<Entity>,
+    velocity: Vec2,
+}
+
+impl Bullet {
+    pub fn new(
+        damage: u32,
+        kind: DamageType,
+        modifiers: Modifiers,
+        knockback: f32,
+        hit_stop: u32,
+        flash: f32,
+    ) -> Self {
+        Bullet {
+            damage,
+            kind,
+            modifiers,
+            knockback,
+            hit_stop,
+            flash,
+            hits: Vec::new(),
+            velocity: Vec2::ZERO,
+        }
+    }
+}
 
 pub fn init(mut commands: Commands, asset_server: Res<AssetServer>) {
     commands
@@ -68,14 +154,46 @@ pub fn init(mut commands: Commands, asset_server: Res<AssetServer>) {
         .insert(Velocity::default())
         .insert(Body::default())
         .insert(Player {
-            damage: 0,
-            health: PLAYER_HEALTH_MAX,
             speed: 768.0,
             aim: Vec2::new(1.0, 0.0),
             direction: 1.0,
+            coyote_time: 0.1,
+            jump_buffer: 0.1,
+            air_jumps: 0,
+            jumping: false,
+            coyote_now: 0.0,
+            jump_buffer_now: 0.1,
+            air_jumps_left: 0,
+            wall_jump_now: PLAYER_WALL_JUMP_TIME,
+            dash_now: PLAYER_DASH_COOLDOWN,
+            dash_max: PLAYER_DASH_COOLDOWN,
+            dash_time_now: PLAYER_DASH_TIME,
+            dash_direction: Vec2::ZERO,
+            afterimage_now: 0.0,
+        })
+        .insert(Health {
             now: PLAYER_INVINCIBILITY,
-            max: PLAYER_INVINCIBILITY,
-        });
+            invincibility: PLAYER_INVINCIBILITY,
+            ..Health::new(PLAYER_HEALTH_MAX)
+        })
+        .insert(Weapon::pistol())
+        .insert(Weapons::default())
+        .insert(Melee::default())
+        .insert(PlayerStats::default());
+
+    commands
+        .spawn_bundle(SpriteBundle {
+            sprite: Sprite {
+                color: CROSSHAIR_COLOR,
+                custom_size: Some(Vec2::new(CROSSHAIR_SIZE, CROSSHAIR_SIZE)),
+                ..Default::default()
+            },
+            texture: asset_server.load("bullet.png"),
+            visibility: Visibility { is_visible: false },
+            ..Default::default()
+        })
+        .insert(GameEntity)
+        .insert(Crosshair);
 }
 
 pub fn movement(
@@ -83,9 +201,17 @@ pub fn movement(
     keyboard_input: Res<Input<KeyCode>>,
     audio: Res<Audio>,
     asset_server: Res<AssetServer>,
-    mut query: Query<(&mut Velocity, &mut Player, &Body)>,
+    time: Res<Time>,
+    mut query: Query<(
+        &mut Velocity,
+        &mut Player,
+        &mut Health,
+        &Body,
+        &PlayerStats,
+        Option<&StatusEffects>,
+    )>,
 ) {
-    let (mut velocity, mut player, body) =
+    let (mut velocity, mut player, mut health, body, stats, effects) =
         if let Ok(result) = query.get_single_mut() {
             result
         } else {
@@ -117,7 +243,15 @@ pub fn movement(
         direction.y += 1.0;
     }
 
-    velocity.0.x = direction.x * player.speed;
+    player.wall_jump_now = (player.wall_jump_now + time.delta_seconds())
+        .min(PLAYER_WALL_JUMP_TIME);
+
+    if player.wall_jump_now >= PLAYER_WALL_JUMP_TIME {
+        velocity.0.x = direction.x
+            * player.speed
+            * stats.speed
+            * effects.map_or(1.0, StatusEffects::speed);
+    }
 
     if direction != Vec2::ZERO {
         player.aim.x = direction.x;
@@ -135,12 +269,174 @@ pub fn movement(
         || keyboard_input.pressed(KeyCode::Space)
         || keyboard_input.pressed(KeyCode::Z);
 
-    if jump_pressed && body.bottom {
+    let jump_just_pressed = mouse_input.just_pressed(MouseButton::Right)
+        || keyboard_input.just_pressed(KeyCode::Space)
+        || keyboard_input.just_pressed(KeyCode::Z);
+
+    let wall = if body.left {
+        -1.0
+    } else if body.right {
+        1.0
+    } else {
+        0.0
+    };
+
+    if body.bottom {
+        player.jumping = false;
+        player.coyote_now = 0.0;
+        player.air_jumps_left = player.air_jumps;
+    } else {
+        player.coyote_now += time.delta_seconds();
+    }
+
+    if jump_just_pressed {
+        player.jump_buffer_now = 0.0;
+    } else {
+        player.jump_buffer_now += time.delta_seconds();
+    }
+
+    let jump_buffered = player.jump_buffer_now < player.jump_buffer;
+
+    if jump_buffered && player.coyote_now < player.coyote_time {
+        audio.play(asset_server.load("jump.wav"));
+        velocity.0.y = PLAYER_JUMP * stats.jump;
+        player.jumping = true;
+        player.coyote_now = player.coyote_time;
+        player.jump_buffer_now = player.jump_buffer;
+    } else if jump_buffered && wall != 0.0 {
+        audio.play(asset_server.load("jump.wav"));
+        velocity.0 =
+            Vec2::new(-wall * PLAYER_WALL_JUMP_SPEED, PLAYER_JUMP * stats.jump);
+        player.direction = -wall;
+        player.jumping = true;
+        player.wall_jump_now = 0.0;
+        player.jump_buffer_now = player.jump_buffer;
+    } else if jump_just_pressed && player.air_jumps_left > 0 {
         audio.play(asset_server.load("jump.wav"));
-        velocity.0.y = 2048.0;
-    } else if !jump_pressed && !body.bottom && velocity.0.y > 0.0 {
+        velocity.0.y = PLAYER_JUMP * stats.jump;
+        player.jumping = true;
+        player.air_jumps_left -= 1;
+        player.jump_buffer_now = player.jump_buffer;
+    } else if player.jumping
+        && !jump_pressed
+        && !body.bottom
+        && velocity.0.y > 0.0
+    {
         velocity.0.y = 0.0;
+        player.jumping = false;
+    }
+
+    if !body.bottom
+        && wall != 0.0
+        && direction.x == wall
+        && velocity.0.y < -PLAYER_WALL_SLIDE_SPEED
+    {
+        velocity.0.y = -PLAYER_WALL_SLIDE_SPEED;
+    }
+
+    player.dash_now =
+        (player.dash_now + time.delta_seconds()).min(player.dash_max);
+
+    if player.dash_now >= player.dash_max
+        && (keyboard_input.just_pressed(KeyCode::LShift)
+            || keyboard_input.just_pressed(KeyCode::RShift))
+    {
+        audio.play(asset_server.load("spring.wav"));
+        player.dash_now = 0.0;
+        player.dash_time_now = 0.0;
+        player.dash_direction = if player.aim != Vec2::ZERO {
+            player.aim.normalize()
+        } else {
+            Vec2::new(player.direction, 0.0)
+        };
+        health.now =
+            health.now.min(health.invincibility - PLAYER_DASH_INVINCIBILITY);
+    }
+
+    if player.dash_time_now < PLAYER_DASH_TIME {
+        player.dash_time_now += time.delta_seconds();
+        velocity.0 = player.dash_direction * PLAYER_DASH_SPEED;
+    }
+}
+
+pub fn aim(
+    settings: Res<Settings>,
+    windows: Res<Windows>,
+    gamepads: Res<Gamepads>,
+    axes: Res<Axis<GamepadAxis>>,
+    camera_query: Query<
+        (&Transform, &OrthographicProjection, &Camera),
+        With<GameCamera>,
+    >,
+    mut player_query: Query<(&mut Player, &Transform), Without<GameCamera>>,
+    mut crosshair_query: Query<
+        (&mut Transform, &mut Visibility),
+        (With<Crosshair>, Without<Player>, Without<GameCamera>),
+    >,
+) {
+    let (mut crosshair_transform, mut crosshair_visibility) =
+        crosshair_query.single_mut();
+
+    let (mut player, player_transform) =
+        if let Ok(result) = player_query.get_single_mut() {
+            result
+        } else {
+            crosshair_visibility.is_visible = false;
+            return;
+        };
+
+    if settings.aim_mode == AimMode::Directional {
+        crosshair_visibility.is_visible = false;
+        return;
     }
+
+    let stick = gamepads
+        .iter()
+        .map(|gamepad| {
+            Vec2::new(
+                axes.get(GamepadAxis(*gamepad, GamepadAxisType::RightStickX))
+                    .unwrap_or(0.0),
+                axes.get(GamepadAxis(*gamepad, GamepadAxisType::RightStickY))
+                    .unwrap_or(0.0),
+            )
+        })
+        .find(|stick| stick.length() > STICK_DEADZONE);
+
+    let (camera_transform, projection, camera) = camera_query.single();
+    let cursor = windows.get(camera.window).and_then(|window| {
+        window.cursor_position().map(|cursor| {
+            Vec2::new(
+                camera_transform.translation.x
+                    + projection.left
+                    + cursor.x / window.width()
+                        * (projection.right - projection.left),
+                camera_transform.translation.y
+                    + projection.bottom
+                    + cursor.y / window.height()
+                        * (projection.top - projection.bottom),
+            )
+        })
+    });
+
+    let target = if let Some(stick) = stick {
+        player.aim = stick.normalize();
+        player_transform.translation.truncate()
+            + player.aim * CROSSHAIR_DISTANCE
+    } else if let Some(cursor) = cursor {
+        let offset = cursor - player_transform.translation.truncate();
+
+        if offset != Vec2::ZERO {
+            player.aim = offset.normalize();
+        }
+
+        cursor
+    } else {
+        crosshair_visibility.is_visible = false;
+        return;
+    };
+
+    crosshair_visibility.is_visible = true;
+    crosshair_transform.translation = target.extend(OVERLAY_LAYER);
 }
 
 pub fn animation(mut query: Query<(&mut Animations, &Velocity), With<Player>>) {
@@ -165,130 +461,507 @@ pub fn shoot(
     keyboard_input: Res<Input<KeyCode>>,
     audio: Res<Audio>,
     asset_server: Res<AssetServer>,
-    query: Query<(&Transform, &Player)>,
+    time: Res<Time>,
+    mut query: Query<(
+        &Transform,
+        &Player,
+        &mut Weapon,
+        &PlayerStats,
+        Option<&Boost>,
+    )>,
 ) {
-    let (transform, player) = if let Ok(result) = query.get_single() {
-        result
+    let (transform, player, mut weapon, stats, boost) =
+        if let Ok(result) = query.get_single_mut() {
+            result
+        } else {
+            return;
+        };
+
+    let modifier = keyboard_input.pressed(KeyCode::LControl);
+    let pressed = mouse_input.pressed(MouseButton::Left) && !modifier
+        || keyboard_input.pressed(KeyCode::X);
+    let charge_pressed = mouse_input.pressed(MouseButton::Left) && modifier
+        || keyboard_input.pressed(KeyCode::V);
+
+    weapon.now =
+        (weapon.now + time.delta_seconds() * stats.fire_rate).min(weapon.max);
+
+    let ready = weapon.now >= weapon.max && weapon.supply.ready();
+
+    let charge = match weapon.charge {
+        Some(charge) if charge_pressed => {
+            if ready {
+                weapon.charging =
+                    (weapon.charging + time.delta_seconds()).min(charge.max);
+            }
+
+            return;
+        }
+        Some(charge) if weapon.charging > 0.0 => {
+            let charged = weapon.charging >= charge.max;
+            weapon.charging = 0.0;
+
+            if charged {
+                Some(charge)
+            } else {
+                None
+            }
+        }
+        _ if pressed && ready => None,
+        _ => return,
+    };
+
+    audio.play(asset_server.load("shoot.wav"));
+    weapon.now = 0.0;
+    weapon.supply.consume();
+
+    let aim = player.aim.y.atan2(player.aim.x);
+
+    let (damage, scale, modifiers) = if let Some(charge) = charge {
+        (
+            weapon.damage * charge.damage,
+            charge.scale,
+            weapon.modifiers + charge.modifiers,
+        )
     } else {
-        return;
+        (weapon.damage, 1.0, weapon.modifiers)
+    };
+    let modifiers = if let Some(boost) = boost {
+        modifiers + boost.modifiers
+    } else {
+        modifiers
     };
 
-    if mouse_input.just_pressed(MouseButton::Left)
-        || keyboard_input.just_pressed(KeyCode::X)
-    {
-        audio.play(asset_server.load("shoot.wav"));
-        commands
-            .spawn_bundle(SpriteBundle {
-                sprite: Sprite {
-                    custom_size: Some(Vec2::new(BULLET_SIZE, BULLET_SIZE)),
-                    ..Default::default()
-                },
-                transform: Transform::from_xyz(
-                    transform.translation.x,
-                    transform.translation.y,
-                    transform.translation.z,
-                )
-                .with_rotation(Quat::from_rotation_z(
-                    player.aim.y.atan2(player.aim.x),
-                )),
-                texture: asset_server.load("bullet.png"),
+    let count = weapon.count + stats.projectiles;
+    let spread = if count > weapon.count {
+        weapon.spread.max(PLAYER_EXTRA_SPREAD * (count - 1) as f32)
+    } else {
+        weapon.spread
+    };
+
+    for index in 0..count {
+        let angle = if count > 1 {
+            aim - spread / 2.0 + spread * index as f32 / (count - 1) as f32
+        } else if weapon.spread > 0.0 {
+            aim + rand::thread_rng()
+                .gen_range(-weapon.spread / 2.0..weapon.spread / 2.0)
+        } else {
+            aim
+        };
+
+        let mut bullet = commands.spawn_bundle(SpriteBundle {
+            sprite: Sprite {
+                color: weapon.projectile.color,
+                custom_size: Some(weapon.projectile.size * scale),
                 ..Default::default()
-            })
+            },
+            transform: Transform::from_xyz(
+                transform.translation.x,
+                transform.translation.y,
+                transform.translation.z,
+            )
+            .with_rotation(Quat::from_rotation_z(angle)),
+            texture: asset_server.load(weapon.projectile.texture),
+            ..Default::default()
+        });
+
+        bullet
             .insert(GameEntity)
-            .insert(Velocity(player.aim.normalize() * 1536.0))
+            .insert(Velocity(
+                Vec2::new(angle.cos(), angle.sin()) * weapon.speed,
+            ))
             .insert(Cleanup)
-            .insert(Bullet);
+            .insert(Bullet::new(
+                damage,
+                DamageType::Projectile,
+                modifiers,
+                weapon.knockback * scale,
+                weapon.hit_stop,
+                weapon.flash,
+            ));
+
+        if weapon.projectile.gravity {
+            bullet.insert(Acceleration(Vec2::new(0.0, GRAVITY)));
+        }
+
+        if modifiers.bounce > 0 {
+            bullet.insert(Body::default());
+        }
     }
 }
 
-pub fn damage(
+pub fn died(
     mut commands: Commands,
-    audio: Res<Audio>,
-    asset_server: Res<AssetServer>,
     mut state: ResMut<State<GameState>>,
-    mut query: Query<(Entity, &mut Player)>,
+    mut events: EventReader<Died>,
+    query: Query<Entity, With<Player>>,
 ) {
-    let (entity, mut player) = if let Ok(result) = query.get_single_mut() {
+    let entity = if let Ok(result) = query.get_single() {
         result
     } else {
         return;
     };
 
-    if player.damage > 0 && player.now >= player.max {
-        audio.play(asset_server.load("damage.wav"));
-        if player.health > player.damage {
-            player.health -= player.damage;
-            player.now = 0.0;
-        } else {
-            state.push(GameState::GameOver).unwrap();
-            commands.entity(entity).despawn();
-        }
+    if events.iter().any(|event| event.entity == entity) {
+        state.overwrite_push(GameState::GameOver).unwrap();
+        commands.entity(entity).despawn();
     }
 }
 
-pub fn invincibility(
+pub fn afterimage(
+    mut commands: Commands,
     time: Res<Time>,
-    mut query: Query<(&mut Player, &mut Sprite)>,
+    mut player_query: Query<(&mut Player, &Transform, &Sprite, &Handle<Image>)>,
+    mut afterimage_query: Query<
+        (Entity, &mut Afterimage, &mut Sprite),
+        Without<Player>,
+    >,
 ) {
-    let (mut player, mut sprite) = if let Ok(result) = query.get_single_mut() {
+    for (entity, mut afterimage, mut sprite) in afterimage_query.iter_mut() {
+        afterimage.now += time.delta_seconds();
+
+        if afterimage.now >= afterimage.max {
+            commands.entity(entity).despawn();
+        } else {
+            sprite.color.set_a(
+                PLAYER_AFTERIMAGE_COLOR.a()
+                    * (1.0 - afterimage.now / afterimage.max),
+            );
+        }
+    }
+
+    let (mut player, transform, sprite, texture) =
+        if let Ok(result) = player_query.get_single_mut() {
+            result
+        } else {
+            return;
+        };
+
+    if player.dash_time_now >= PLAYER_DASH_TIME {
+        player.afterimage_now = PLAYER_AFTERIMAGE_INTERVAL;
+        return;
+    }
+
+    player.afterimage_now += time.delta_seconds();
+
+    if player.afterimage_now >= PLAYER_AFTERIMAGE_INTERVAL {
+        player.afterimage_now = 0.0;
+
+        commands
+            .spawn_bundle(SpriteBundle {
+                sprite: Sprite {
+                    color: PLAYER_AFTERIMAGE_COLOR,
+                    flip_x: sprite.flip_x,
+                    custom_size: sprite.custom_size,
+                    ..Default::default()
+                },
+                transform: Transform::from_xyz(
+                    transform.translation.x,
+                    transform.translation.y,
+                    transform.translation.z - 0.1,
+                ),
+                texture: texture.clone(),
+                ..Default::default()
+            })
+            .insert(GameEntity)
+            .insert(Afterimage { now: 0.0, max: PLAYER_AFTERIMAGE_TIME });
+    }
+}
+
+pub fn invincibility(mut query: Query<(&Health, &mut Sprite), With<Player>>) {
+    let (health, mut sprite) = if let Ok(result) = query.get_single_mut() {
         result
     } else {
         return;
     };
 
-    if player.now < player.max {
-        player.now += time.delta_seconds();
-
-        if player.now >= player.max {
-            player.damage = 0;
-            sprite.color.set_a(1.0);
+    if health.invincible() {
+        let m = health.now % PLAYER_FLASH_FREQUENCY / PLAYER_FLASH_FREQUENCY;
+        if m < 0.5 {
+            sprite.color.set_a(1.0 - m);
         } else {
-            let m =
-                player.now % PLAYER_FLASH_FREQUENCY / PLAYER_FLASH_FREQUENCY;
-            if m < 0.5 {
-                sprite.color.set_a(1.0 - m);
-            } else {
-                sprite.color.set_a(m);
-            }
+            sprite.color.set_a(m);
         }
+    } else if sprite.color.a() != 1.0 {
+        sprite.color.set_a(1.0);
     }
 }
 
 pub fn bullet(
     mut commands: Commands,
-    mut score: ResMut<Score>,
+    mut died: EventWriter<Died>,
+    mut hit_stop: ResMut<HitStop>,
     audio: Res<Audio>,
     asset_server: Res<AssetServer>,
-    bullet_query: Query<(Entity, &Transform, &Sprite), With<Bullet>>,
+    mut bullet_query: Query<(
+        Entity,
+        &Transform,
+        &Sprite,
+        &Velocity,
+        &mut Bullet,
+    )>,
     mut enemy_query: Query<
-        (Entity, &mut Enemy, &Transform, &Sprite),
-        Without<Spawning>,
+        (
+            Entity,
+            &mut Health,
+            Option<&mut Velocity>,
+            &mut Sprite,
+            &Transform,
+            Option<&Shielded>,
+            Option<&HitFeedback>,
+            Option<&Flash>,
+            Option<&mut StatusEffects>,
+        ),
+        (
+            With<Enemy>,
+            Without<Spawning>,
+            Without<Dead>,
+            Without<Bullet>,
+            Without<Player>,
+        ),
     >,
 ) {
-    for (bullet_entity, bullet_transform, bullet_sprite) in bullet_query.iter()
+    for (
+        bullet_entity,
+        bullet_transform,
+        bullet_sprite,
+        bullet_velocity,
+        mut bullet,
+    ) in bullet_query.iter_mut()
     {
-        for (enemy_entity, mut enemy, enemy_transform, enemy_sprite) in
-            enemy_query.iter_mut()
+        for (
+            enemy_entity,
+            mut health,
+            mut enemy_velocity,
+            mut enemy_sprite,
+            enemy_transform,
+            shielded,
+            feedback,
+            flash,
+            mut effects,
+        ) in enemy_query.iter_mut()
         {
-            if collide_aabb::collide(
-                bullet_transform.translation,
-                bullet_sprite.custom_size.unwrap(),
-                enemy_transform.translation,
-                enemy_sprite.custom_size.unwrap(),
-            )
-            .is_some()
+            if !bullet.hits.contains(&enemy_entity)
+                && collide_aabb::collide(
+                    bullet_transform.translation,
+                    bullet_sprite.custom_size.unwrap(),
+                    enemy_transform.translation,
+                    enemy_sprite.custom_size.unwrap(),
+                )
+                .is_some()
             {
-                commands.entity(bullet_entity).despawn();
+                let facing = if enemy_sprite.flip_x { -1.0 } else { 1.0 };
 
-                if enemy.health > 1 {
-                    audio.play(asset_server.load("hit.wav"));
-                    enemy.health -= 1;
+                let blocked = shielded.is_some()
+                    && bullet.kind != DamageType::Explosion
+                    && (bullet_transform.translation.x
+                        - enemy_transform.translation.x)
+                        * facing
+                        > 0.0;
+
+                if bullet.modifiers.explode > 0.0 {
+                    audio.play(asset_server.load("damage.wav"));
+                    commands
+                        .spawn_bundle(SpriteBundle {
+                            sprite: Sprite {
+                                color: EXPLOSION_COLOR,
+                                custom_size: Some(Vec2::new(
+                                    bullet.modifiers.explode * 2.0,
+                                    bullet.modifiers.explode * 2.0,
+                                )),
+                                ..Default::default()
+                            },
+                            transform: *bullet_transform,
+                            texture: asset_server.load("bullet.png"),
+                            ..Default::default()
+                        })
+                        .insert(GameEntity)
+                        .insert(Velocity::default())
+                        .insert(Lifetime { now: 0.0, max: EXPLOSION_TIME })
+                        .insert(Bullet {
+                            damage: bullet.damage,
+                            kind: DamageType::Explosion,
+                            modifiers: Modifiers {
+                                pierce: u32::MAX,
+                                bounce: 0,
+                                homing: 0.0,
+                                explode: 0.0,
+                                ..bullet.modifiers
+                            },
+                            knockback: 0.0,
+                            hit_stop: bullet.hit_stop,
+                            flash: bullet.flash,
+                            hits: if blocked {
+                                Vec::new()
+                            } else {
+                                vec![enemy_entity]
+                            },
+                            velocity: Vec2::ZERO,
+                        });
+                }
+
+                if blocked {
+                    commands.entity(bullet_entity).despawn();
+                    break;
+                }
+
+                let spent = bullet.modifiers.pierce == 0;
+
+                if spent {
+                    commands.entity(bullet_entity).despawn();
                 } else {
-                    audio.play(asset_server.load("kill.wav"));
-                    score.0 += 1;
-                    commands.entity(enemy_entity).despawn();
+                    bullet.modifiers.pierce -= 1;
+                    bullet.hits.push(enemy_entity);
+                }
+
+                let dealt = health.damage(bullet.damage, bullet.kind);
+
+                if health.current > 0 {
+                    status::inflict(
+                        &mut commands,
+                        enemy_entity,
+                        effects.as_deref_mut(),
+                        &bullet.modifiers,
+                        bullet.kind,
+                    );
+
+                    audio.play(asset_server.load("hit.wav"));
+
+                    let default_feedback = HitFeedback::default();
+                    let feedback = feedback.unwrap_or(&default_feedback);
+
+                    let duration = feedback.flash * bullet.flash;
+
+                    if duration > 0.0 {
+                        let color = if let Some(flash) = flash {
+                            flash.color
+                        } else {
+                            enemy_sprite.color
+                        };
+
+                        enemy_sprite.color = ENEMY_FLASH_COLOR;
+                        commands.entity(enemy_entity).insert(Flash {
+                            now: 0.0,
+                            max: duration,
+                            color,
+                        });
+                    }
+
+                    if let (Some(enemy_velocity), true) = (
+                        enemy_velocity.as_mut(),
+                        bullet.knockback * feedback.knockback > 0.0,
+                    ) {
+                        enemy_velocity.0 +=
+                            bullet_velocity.0.normalize_or_zero()
+                                * bullet.knockback
+                                * feedback.knockback;
+                        commands.entity(enemy_entity).insert(Knockback {
+                            now: 0.0,
+                            max: ENEMY_KNOCKBACK_TIME,
+                        });
+                    }
+
+                    if feedback.hit_stop {
+                        hit_stop.ticks = hit_stop.ticks.max(bullet.hit_stop);
+                    }
+                } else if dealt > 0 {
+                    if let Some(enemy_velocity) = enemy_velocity.as_mut() {
+                        enemy_velocity.0 =
+                            bullet_velocity.0.normalize_or_zero()
+                                * ENEMY_DEATH_KNOCKBACK
+                                + Vec2::new(0.0, ENEMY_DEATH_KNOCKBACK);
+                    }
+
+                    died.send(Died { entity: enemy_entity, kind: bullet.kind });
+                }
+
+                if spent {
+                    break;
                 }
             }
         }
     }
 }
+
+pub fn bounce(
+    mut commands: Commands,
+    mut query: Query<(
+        Entity,
+        &mut Transform,
+        &mut Velocity,
+        &mut Bullet,
+        &Body,
+    )>,
+) {
+    for (entity, mut transform, mut velocity, mut bullet, body) in
+        query.iter_mut()
+    {
+        let previous = bullet.velocity;
+        let mut bounced = false;
+
+        if body.left && previous.x < 0.0 || body.right && previous.x > 0.0 {
+            velocity.0.x = -previous.x;
+            bounced = true;
+        }
+
+        if body.bottom && previous.y < 0.0 || body.top && previous.y > 0.0 {
+            velocity.0.y = -previous.y;
+            bounced = true;
+        }
+
+        if bounced {
+            bullet.modifiers.bounce = bullet.modifiers.bounce.saturating_sub(1);
+            transform.rotation =
+                Quat::from_rotation_z(velocity.0.y.atan2(velocity.0.x));
+
+            if bullet.modifiers.bounce == 0 {
+                commands.entity(entity).remove::<Body>();
+            }
+        }
+
+        bullet.velocity = velocity.0;
+    }
+}
+
+pub fn homing(
+    enemy_query: Query<
+        &Transform,
+        (With<Enemy>, Without<Spawning>, Without<Dead>, Without<Bullet>),
+    >,
+    mut bullet_query: Query<(&mut Transform, &mut Velocity, &Bullet)>,
+) {
+    for (mut transform, mut velocity, bullet) in bullet_query.iter_mut() {
+        if bullet.modifiers.homing <= 0.0 || velocity.0 == Vec2::ZERO {
+            continue;
+        }
+
+        let translation = transform.translation.truncate();
+        let target = enemy_query
+            .iter()
+            .map(|enemy_transform| enemy_transform.translation.truncate())
+            .min_by(|a, b| {
+                a.distance_squared(translation)
+                    .partial_cmp(&b.distance_squared(translation))
+                    .unwrap()
+            });
+
+        if let Some(target) = target {
+            let offset = target - translation;
+            let angle = velocity.0.y.atan2(velocity.0.x);
+            let mut delta = offset.y.atan2(offset.x) - angle;
+
+            if delta > PI {
+                delta -= 2.0 * PI;
+            } else if delta < -PI {
+                delta += 2.0 * PI;
+            }
+
+            let step = bullet.modifiers.homing * TIME_STEP;
+            let angle = angle + delta.clamp(-step, step);
+
+            velocity.0 =
+                Vec2::new(angle.cos(), angle.sin()) * velocity.0.length();
+            transform.rotation = Quat::from_rotation_z(angle);
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use bevy::prelude::*;
+
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+pub enum AimMode {
+    #[default]
+    Directional,
+    Free,
+}
+
+#[derive(Default)]
+pub struct Settings {
+    pub spawn_min_distance: Option<f32>,
+    pub spawn_at_edges: bool,
+    pub spawn_in_sky: bool,
+    pub spawn_warning: bool,
+    pub health_bars: bool,
+    pub damage_numbers: bool,
+    pub aim_mode: AimMode,
+}
+
+const SPAWN_MIN_DISTANCE: f32 = 512.0;
+
+pub fn toggle(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut settings: ResMut<Settings>,
+) {
+    if keyboard_input.just_pressed(KeyCode::F1) {
+        settings.spawn_min_distance = match settings.spawn_min_distance {
+            Some(_) => None,
+            None => Some(SPAWN_MIN_DISTANCE),
+        };
+    }
+
+    if keyboard_input.just_pressed(KeyCode::F2) {
+        settings.spawn_at_edges = !settings.spawn_at_edges;
+    }
+
+    if keyboard_input.just_pressed(KeyCode::F3) {
+        settings.spawn_in_sky = !settings.spawn_in_sky;
+    }
+
+    if keyboard_input.just_pressed(KeyCode::F4) {
+        settings.spawn_warning = !settings.spawn_warning;
+    }
+
+    if keyboard_input.just_pressed(KeyCode::F5) {
+        settings.health_bars = !settings.health_bars;
+    }
+
+    if keyboard_input.just_pressed(KeyCode::F6) {
+        settings.damage_numbers = !settings.damage_numbers;
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Tab) {
+        settings.aim_mode = match settings.aim_mode {
+            AimMode::Directional => AimMode::Free,
+            AimMode::Free => AimMode::Directional,
+        };
+    }
+}
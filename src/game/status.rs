@@ -0,0 +1,222 @@
+use bevy::prelude::*;
+
+use super::{
+    animation::Flash,
+    enemy::Dead,
+    health::{DamageType, Died, Health},
+    weapon::Modifiers,
+};
+
+const BURN_INTERVAL: f32 = 0.5;
+const BURN_STACKS: u32 = 3;
+const BURN_COLOR: Color = Color::rgb(1.0, 0.5, 0.3);
+const FREEZE_SPEED: f32 = 0.25;
+const FREEZE_COLOR: Color = Color::rgb(0.5, 0.8, 1.0);
+const SLOW_SPEED: f32 = 0.6;
+const SLOW_COLOR: Color = Color::rgb(0.7, 0.6, 1.0);
+const POISON_INTERVAL: f32 = 1.0;
+const POISON_STACKS: u32 = 5;
+const POISON_COLOR: Color = Color::rgb(0.5, 1.0, 0.4);
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum StatusKind {
+    Burn,
+    Freeze,
+    Slow,
+    Poison,
+}
+
+impl StatusKind {
+    fn interval(self) -> f32 {
+        match self {
+            StatusKind::Burn => BURN_INTERVAL,
+            StatusKind::Poison => POISON_INTERVAL,
+            StatusKind::Freeze | StatusKind::Slow => 0.0,
+        }
+    }
+
+    fn max_stacks(self) -> u32 {
+        match self {
+            StatusKind::Burn => BURN_STACKS,
+            StatusKind::Poison => POISON_STACKS,
+            StatusKind::Freeze | StatusKind::Slow => 1,
+        }
+    }
+
+    fn speed(self) -> f32 {
+        match self {
+            StatusKind::Freeze => FREEZE_SPEED,
+            StatusKind::Slow => SLOW_SPEED,
+            StatusKind::Burn | StatusKind::Poison => 1.0,
+        }
+    }
+
+    fn color(self) -> Color {
+        match self {
+            StatusKind::Burn => BURN_COLOR,
+            StatusKind::Freeze => FREEZE_COLOR,
+            StatusKind::Slow => SLOW_COLOR,
+            StatusKind::Poison => POISON_COLOR,
+        }
+    }
+
+    fn opposite(self) -> Option<StatusKind> {
+        match self {
+            StatusKind::Burn => Some(StatusKind::Freeze),
+            StatusKind::Freeze => Some(StatusKind::Burn),
+            StatusKind::Slow | StatusKind::Poison => None,
+        }
+    }
+}
+
+pub struct Status {
+    kind: StatusKind,
+    source: DamageType,
+    stacks: u32,
+    tick_now: f32,
+    now: f32,
+    max: f32,
+}
+
+#[derive(Default, Component)]
+pub struct StatusEffects {
+    effects: Vec<Status>,
+    color: Option<Color>,
+}
+
+impl StatusEffects {
+    pub fn apply(
+        &mut self,
+        kind: StatusKind,
+        duration: f32,
+        source: DamageType,
+    ) {
+        if let Some(opposite) = kind.opposite() {
+            self.effects.retain(|status| status.kind != opposite);
+        }
+
+        if let Some(index) =
+            self.effects.iter().position(|status| status.kind == kind)
+        {
+            let mut status = self.effects.remove(index);
+            status.stacks = (status.stacks + 1).min(kind.max_stacks());
+            status.source = source;
+            status.now = 0.0;
+            status.max = status.max.max(duration);
+            self.effects.push(status);
+        } else {
+            self.effects.push(Status {
+                kind,
+                source,
+                stacks: 1,
+                tick_now: 0.0,
+                now: 0.0,
+                max: duration,
+            });
+        }
+    }
+
+    pub fn inflict(&mut self, modifiers: &Modifiers, source: DamageType) {
+        for (kind, duration) in [
+            (StatusKind::Burn, modifiers.burn),
+            (StatusKind::Freeze, modifiers.freeze),
+            (StatusKind::Slow, modifiers.slow),
+            (StatusKind::Poison, modifiers.poison),
+        ] {
+            if duration > 0.0 {
+                self.apply(kind, duration, source);
+            }
+        }
+    }
+
+    pub fn base_color(&self) -> Option<Color> {
+        self.color
+    }
+
+    pub fn speed(&self) -> f32 {
+        self.effects
+            .iter()
+            .map(|status| status.kind.speed())
+            .fold(1.0, f32::min)
+    }
+}
+
+pub fn inflict(
+    commands: &mut Commands,
+    entity: Entity,
+    effects: Option<&mut StatusEffects>,
+    modifiers: &Modifiers,
+    source: DamageType,
+) {
+    if modifiers.burn <= 0.0
+        && modifiers.freeze <= 0.0
+        && modifiers.slow <= 0.0
+        && modifiers.poison <= 0.0
+    {
+        return;
+    }
+
+    if let Some(effects) = effects {
+        effects.inflict(modifiers, source);
+    } else {
+        let mut effects = StatusEffects::default();
+        effects.inflict(modifiers, source);
+        commands.entity(entity).insert(effects);
+    }
+}
+
+pub fn tick(
+    time: Res<Time>,
+    mut died: EventWriter<Died>,
+    mut query: Query<(Entity, &mut Health, &mut StatusEffects), Without<Dead>>,
+) {
+    for (entity, mut health, mut effects) in query.iter_mut() {
+        for status in effects.effects.iter_mut() {
+            status.now += time.delta_seconds();
+
+            let interval = status.kind.interval();
+
+            if interval <= 0.0 {
+                continue;
+            }
+
+            status.tick_now += time.delta_seconds();
+
+            while status.tick_now >= interval {
+                status.tick_now -= interval;
+
+                if health.damage(status.stacks, status.source) > 0
+                    && health.current == 0
+                {
+                    died.send(Died { entity, kind: status.source });
+                }
+            }
+        }
+
+        effects.effects.retain(|status| status.now < status.max);
+    }
+}
+
+pub fn tint(
+    mut query: Query<(&mut StatusEffects, &mut Sprite), Without<Flash>>,
+) {
+    for (mut effects, mut sprite) in query.iter_mut() {
+        let alpha = sprite.color.a();
+
+        if let Some(status) = effects.effects.last() {
+            let color = status.kind.color();
+
+            if effects.color.is_none() {
+                effects.color = Some(sprite.color);
+            }
+
+            sprite.color = color;
+        } else if let Some(color) = effects.color.take() {
+            sprite.color = color;
+        } else {
+            continue;
+        }
+
+        sprite.color.set_a(alpha);
+    }
+}
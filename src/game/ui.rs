@@ -3,19 +3,75 @@ use bevy::prelude::*;
 use crate::plugin::FONT;
 
 use super::{
-    enemy::Score,
-    entity::GameEntity,
-    player::{Player, PLAYER_HEALTH_MAX},
+    boss::Boss,
+    enemy::{Dead, Enemy, Score},
+    entity::{GameEntity, OVERLAY_LAYER},
+    health::Health,
+    pickup::{PICKUP_SHIELD_COLOR, PICKUP_SHIELD_MAX},
+    player::{Player, PLAYER_HEALTH_LIMIT, PLAYER_HEALTH_MAX},
+    settings::Settings,
+    weapon::{Supply, Weapon},
 };
 
 const HEALTH_SIZE: f32 = 64.0;
+const BOSS_HEALTH_WIDTH: f32 = 960.0;
+const BOSS_HEALTH_HEIGHT: f32 = 32.0;
+const DASH_READY_COLOR: Color = Color::rgb(0.5, 0.8, 1.0);
+const DASH_COOLDOWN_COLOR: Color = Color::rgba(0.5, 0.5, 0.5, 0.5);
+const ENEMY_HEALTH_WIDTH: f32 = 96.0;
+const ENEMY_HEALTH_HEIGHT: f32 = 12.0;
+const ENEMY_HEALTH_MARGIN: f32 = 16.0;
+const DAMAGE_NUMBER_SIZE: f32 = 48.0;
+const DAMAGE_NUMBER_SPEED: f32 = 128.0;
+const DAMAGE_NUMBER_TIME: f32 = 0.75;
 
 #[derive(Component)]
 pub struct HealthIndex(u32);
 
+#[derive(Component)]
+pub struct ShieldIndex(u32);
+
 #[derive(Component)]
 pub struct ScoreText;
 
+#[derive(Component)]
+pub struct WeaponText;
+
+#[derive(Component)]
+pub struct SupplyText;
+
+#[derive(Component)]
+pub struct DashIndicator;
+
+#[derive(Component)]
+pub struct BossHealthBar;
+
+#[derive(Component)]
+pub struct BossHealth;
+
+#[derive(Component)]
+pub struct Damage {
+    health: u32,
+    bar: bool,
+}
+
+#[derive(Component)]
+pub struct EnemyHealthBar {
+    enemy: Entity,
+    offset: f32,
+}
+
+#[derive(Component)]
+pub struct EnemyHealth {
+    enemy: Entity,
+}
+
+#[derive(Component)]
+pub struct DamageNumber {
+    now: f32,
+    max: f32,
+}
+
 pub fn init(mut commands: Commands, asset_server: Res<AssetServer>) {
     commands
         .spawn_bundle(NodeBundle {
@@ -42,7 +98,7 @@ pub fn init(mut commands: Commands, asset_server: Res<AssetServer>) {
                     ..Default::default()
                 })
                 .with_children(|children| {
-                    for index in 0..PLAYER_HEALTH_MAX {
+                    for index in 0..PLAYER_HEALTH_LIMIT {
                         children
                             .spawn_bundle(ImageBundle {
                                 style: Style {
@@ -50,6 +106,11 @@ pub fn init(mut commands: Commands, asset_server: Res<AssetServer>) {
                                         Val::Px(HEALTH_SIZE),
                                         Val::Px(HEALTH_SIZE),
                                     ),
+                                    display: if index < PLAYER_HEALTH_MAX {
+                                        Display::Flex
+                                    } else {
+                                        Display::None
+                                    },
                                     ..Default::default()
                                 },
                                 image: asset_server.load("bullet.png").into(),
@@ -57,8 +118,72 @@ pub fn init(mut commands: Commands, asset_server: Res<AssetServer>) {
                             })
                             .insert(HealthIndex(index));
                     }
+
+                    for index in 0..PICKUP_SHIELD_MAX {
+                        children
+                            .spawn_bundle(ImageBundle {
+                                style: Style {
+                                    size: Size::new(
+                                        Val::Px(HEALTH_SIZE),
+                                        Val::Px(HEALTH_SIZE),
+                                    ),
+                                    display: Display::None,
+                                    ..Default::default()
+                                },
+                                color: PICKUP_SHIELD_COLOR.into(),
+                                image: asset_server.load("bullet.png").into(),
+                                ..Default::default()
+                            })
+                            .insert(ShieldIndex(index));
+                    }
+
+                    children
+                        .spawn_bundle(ImageBundle {
+                            style: Style {
+                                size: Size::new(
+                                    Val::Px(HEALTH_SIZE / 2.0),
+                                    Val::Px(HEALTH_SIZE / 2.0),
+                                ),
+                                margin: Rect::all(Val::Px(HEALTH_SIZE / 4.0)),
+                                ..Default::default()
+                            },
+                            color: DASH_READY_COLOR.into(),
+                            image: asset_server.load("bullet.png").into(),
+                            ..Default::default()
+                        })
+                        .insert(DashIndicator);
+
+                    children
+                        .spawn_bundle(TextBundle {
+                            text: Text::with_section(
+                                "",
+                                TextStyle {
+                                    font: asset_server.load(FONT),
+                                    font_size: 48.0,
+                                    color: Color::WHITE,
+                                },
+                                Default::default(),
+                            ),
+                            ..Default::default()
+                        })
+                        .insert(SupplyText);
                 });
 
+            children
+                .spawn_bundle(TextBundle {
+                    text: Text::with_section(
+                        "",
+                        TextStyle {
+                            font: asset_server.load(FONT),
+                            font_size: 64.0,
+                            color: Color::WHITE,
+                        },
+                        Default::default(),
+                    ),
+                    ..Default::default()
+                })
+                .insert(WeaponText);
+
             children
                 .spawn_bundle(TextBundle {
                     text: Text {
@@ -86,24 +211,97 @@ pub fn init(mut commands: Commands, asset_server: Res<AssetServer>) {
                 })
                 .insert(ScoreText);
         });
+
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                justify_content: JustifyContent::Center,
+                position: Rect {
+                    bottom: Val::Px(BOSS_HEALTH_HEIGHT),
+                    ..Default::default()
+                },
+                size: Size::new(Val::Percent(100.0), Val::Auto),
+                ..Default::default()
+            },
+            color: Color::NONE.into(),
+            ..Default::default()
+        })
+        .insert(GameEntity)
+        .with_children(|children| {
+            children
+                .spawn_bundle(NodeBundle {
+                    style: Style {
+                        size: Size::new(
+                            Val::Px(BOSS_HEALTH_WIDTH),
+                            Val::Px(BOSS_HEALTH_HEIGHT),
+                        ),
+                        ..Default::default()
+                    },
+                    color: Color::rgb(0.25, 0.25, 0.25).into(),
+                    visibility: Visibility { is_visible: false },
+                    ..Default::default()
+                })
+                .insert(BossHealthBar)
+                .with_children(|children| {
+                    children
+                        .spawn_bundle(NodeBundle {
+                            style: Style {
+                                size: Size::new(
+                                    Val::Percent(100.0),
+                                    Val::Percent(100.0),
+                                ),
+                                ..Default::default()
+                            },
+                            color: Color::RED.into(),
+                            visibility: Visibility { is_visible: false },
+                            ..Default::default()
+                        })
+                        .insert(BossHealthBar)
+                        .insert(BossHealth);
+                });
+        });
 }
 
 pub fn health(
-    player_query: Query<&Player>,
-    mut health_query: Query<(&mut Visibility, &HealthIndex)>,
+    player_query: Query<&Health, With<Player>>,
+    mut health_query: Query<(&mut Visibility, &mut Style, &HealthIndex)>,
 ) {
-    let health = if let Ok(player) = player_query.get_single() {
-        player.health
+    let (current, max) = if let Ok(health) = player_query.get_single() {
+        (health.current, health.max)
     } else {
-        0
+        (0, PLAYER_HEALTH_MAX)
     };
 
-    for (mut visibility, HealthIndex(index)) in health_query.iter_mut() {
-        let is_visible = *index < health;
+    for (mut visibility, mut style, HealthIndex(index)) in
+        health_query.iter_mut()
+    {
+        let is_visible = *index < current;
+        let display = if *index < max { Display::Flex } else { Display::None };
 
         if visibility.is_visible != is_visible {
             visibility.is_visible = is_visible;
         }
+
+        if style.display != display {
+            style.display = display;
+        }
+    }
+}
+
+pub fn shield(
+    player_query: Query<&Health, With<Player>>,
+    mut shield_query: Query<(&mut Style, &ShieldIndex)>,
+) {
+    let shield = player_query.get_single().map_or(0, |health| health.shield);
+
+    for (mut style, ShieldIndex(index)) in shield_query.iter_mut() {
+        let display =
+            if *index < shield { Display::Flex } else { Display::None };
+
+        if style.display != display {
+            style.display = display;
+        }
     }
 }
 
@@ -112,3 +310,251 @@ pub fn score(score: Res<Score>, mut query: Query<&mut Text, With<ScoreText>>) {
 
     text.sections[1].value = score.0.to_string();
 }
+
+pub fn weapon(
+    weapon_query: Query<&Weapon, (With<Player>, Changed<Weapon>)>,
+    mut text_query: Query<&mut Text, With<WeaponText>>,
+) {
+    if let Ok(weapon) = weapon_query.get_single() {
+        let mut text = text_query.single_mut();
+
+        if text.sections[0].value != weapon.name {
+            text.sections[0].value = weapon.name.to_string();
+        }
+    }
+}
+
+pub fn dash(
+    player_query: Query<&Player, Changed<Player>>,
+    mut indicator_query: Query<&mut UiColor, With<DashIndicator>>,
+) {
+    if let Ok(player) = player_query.get_single() {
+        let mut color = indicator_query.single_mut();
+        let target = if player.dash_now >= player.dash_max {
+            DASH_READY_COLOR
+        } else {
+            DASH_COOLDOWN_COLOR
+        };
+
+        if color.0 != target {
+            color.0 = target;
+        }
+    }
+}
+
+pub fn supply(
+    weapon_query: Query<&Weapon, (With<Player>, Changed<Weapon>)>,
+    mut text_query: Query<&mut Text, With<SupplyText>>,
+) {
+    if let Ok(weapon) = weapon_query.get_single() {
+        let mut text = text_query.single_mut();
+
+        let (value, color) = match weapon.supply {
+            Supply::Unlimited => (String::new(), Color::WHITE),
+            Supply::Magazine { now, max, .. } if now < max => {
+                ("Reloading".to_string(), Color::GRAY)
+            }
+            Supply::Magazine { size, ammo, .. } => {
+                (format!("{}/{}", ammo, size), Color::WHITE)
+            }
+            Supply::Heat { overheated: true, .. } => {
+                ("Overheated".to_string(), Color::RED)
+            }
+            Supply::Heat { heat, .. } => (
+                format!("Heat {}%", (heat * 100.0).round()),
+                Color::rgb(1.0, 1.0 - heat, 1.0 - heat),
+            ),
+        };
+
+        if text.sections[0].value != value {
+            text.sections[0].value = value;
+        }
+
+        if text.sections[0].style.color != color {
+            text.sections[0].style.color = color;
+        }
+    }
+}
+
+pub fn boss_health(
+    boss_query: Query<&Health, (With<Boss>, Without<Dead>)>,
+    mut bar_query: Query<&mut Visibility, With<BossHealthBar>>,
+    mut health_query: Query<&mut Style, With<BossHealth>>,
+) {
+    let health = boss_query
+        .get_single()
+        .ok()
+        .map(|health| health.current as f32 / health.max as f32 * 100.0);
+
+    for mut visibility in bar_query.iter_mut() {
+        if visibility.is_visible != health.is_some() {
+            visibility.is_visible = health.is_some();
+        }
+    }
+
+    if let Some(health) = health {
+        let mut style = health_query.single_mut();
+
+        if style.size.width != Val::Percent(health) {
+            style.size.width = Val::Percent(health);
+        }
+    }
+}
+
+pub fn damage(
+    mut commands: Commands,
+    settings: Res<Settings>,
+    asset_server: Res<AssetServer>,
+    mut query: Query<
+        (Entity, &Health, &Transform, &Sprite, Option<&mut Damage>),
+        (With<Enemy>, Changed<Health>, Without<Boss>),
+    >,
+) {
+    for (entity, health, transform, sprite, damage) in query.iter_mut() {
+        let mut damage = if let Some(damage) = damage {
+            damage
+        } else {
+            commands
+                .entity(entity)
+                .insert(Damage { health: health.current, bar: false });
+            continue;
+        };
+
+        if health.current < damage.health {
+            if settings.damage_numbers {
+                commands
+                    .spawn_bundle(Text2dBundle {
+                        text: Text::with_section(
+                            (damage.health - health.current).to_string(),
+                            TextStyle {
+                                font: asset_server.load(FONT),
+                                font_size: DAMAGE_NUMBER_SIZE,
+                                color: Color::WHITE,
+                            },
+                            TextAlignment {
+                                vertical: VerticalAlign::Center,
+                                horizontal: HorizontalAlign::Center,
+                            },
+                        ),
+                        transform: Transform::from_xyz(
+                            transform.translation.x,
+                            transform.translation.y,
+                            OVERLAY_LAYER,
+                        ),
+                        ..Default::default()
+                    })
+                    .insert(GameEntity)
+                    .insert(DamageNumber { now: 0.0, max: DAMAGE_NUMBER_TIME });
+            }
+
+            if settings.health_bars
+                && !damage.bar
+                && health.current > 0
+                && health.max > 1
+            {
+                damage.bar = true;
+
+                commands
+                    .spawn_bundle(SpriteBundle {
+                        sprite: Sprite {
+                            color: Color::rgb(0.25, 0.25, 0.25),
+                            custom_size: Some(Vec2::new(
+                                ENEMY_HEALTH_WIDTH,
+                                ENEMY_HEALTH_HEIGHT,
+                            )),
+                            ..Default::default()
+                        },
+                        transform: Transform::from_xyz(
+                            transform.translation.x,
+                            transform.translation.y,
+                            OVERLAY_LAYER,
+                        ),
+                        ..Default::default()
+                    })
+                    .insert(GameEntity)
+                    .insert(EnemyHealthBar {
+                        enemy: entity,
+                        offset: sprite.custom_size.unwrap().y / 2.0
+                            + ENEMY_HEALTH_MARGIN,
+                    })
+                    .with_children(|children| {
+                        children
+                            .spawn_bundle(SpriteBundle {
+                                sprite: Sprite {
+                                    color: Color::RED,
+                                    custom_size: Some(Vec2::new(
+                                        ENEMY_HEALTH_WIDTH,
+                                        ENEMY_HEALTH_HEIGHT,
+                                    )),
+                                    ..Default::default()
+                                },
+                                transform: Transform::from_xyz(0.0, 0.0, 0.1),
+                                ..Default::default()
+                            })
+                            .insert(EnemyHealth { enemy: entity });
+                    });
+            }
+        }
+
+        damage.health = health.current;
+    }
+}
+
+pub fn enemy_health(
+    mut commands: Commands,
+    settings: Res<Settings>,
+    enemy_query: Query<(&Health, &Transform), (With<Enemy>, Without<Dead>)>,
+    mut bar_query: Query<
+        (Entity, &EnemyHealthBar, &mut Transform),
+        Without<Enemy>,
+    >,
+    mut health_query: Query<
+        (&EnemyHealth, &mut Sprite, &mut Transform),
+        (Without<Enemy>, Without<EnemyHealthBar>),
+    >,
+    mut damage_query: Query<&mut Damage>,
+) {
+    for (entity, bar, mut transform) in bar_query.iter_mut() {
+        if let (Ok((_, enemy_transform)), true) =
+            (enemy_query.get(bar.enemy), settings.health_bars)
+        {
+            transform.translation.x = enemy_transform.translation.x;
+            transform.translation.y =
+                enemy_transform.translation.y + bar.offset;
+        } else {
+            if let Ok(mut damage) = damage_query.get_mut(bar.enemy) {
+                damage.bar = false;
+            }
+
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+
+    for (bar, mut sprite, mut transform) in health_query.iter_mut() {
+        if let Ok((health, _)) = enemy_query.get(bar.enemy) {
+            let width = ENEMY_HEALTH_WIDTH
+                * (health.current as f32 / health.max as f32).min(1.0);
+
+            sprite.custom_size = Some(Vec2::new(width, ENEMY_HEALTH_HEIGHT));
+            transform.translation.x = (width - ENEMY_HEALTH_WIDTH) / 2.0;
+        }
+    }
+}
+
+pub fn damage_number(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut DamageNumber, &mut Transform, &mut Text)>,
+) {
+    for (entity, mut number, mut transform, mut text) in query.iter_mut() {
+        number.now += time.delta_seconds();
+
+        if number.now >= number.max {
+            commands.entity(entity).despawn();
+        } else {
+            transform.translation.y +=
+                DAMAGE_NUMBER_SPEED * time.delta_seconds();
+            text.sections[0].style.color.set_a(1.0 - number.now / number.max);
+        }
+    }
+}
@@ -0,0 +1,276 @@
+use bevy::prelude::*;
+use rand::seq::SliceRandom;
+
+use crate::{plugin::FONT, GameState};
+
+use super::{
+    enemy::{Dead, Enemy},
+    health::{DamageType, Died, Health},
+    player::{Player, PLAYER_HEALTH_LIMIT},
+};
+
+const UPGRADE_INTERVAL: u32 = 10;
+const UPGRADE_CHOICES: usize = 3;
+const UPGRADE_FIRE_RATE: f32 = 0.2;
+const UPGRADE_INVINCIBILITY: f32 = 0.5;
+const UPGRADE_JUMP: f32 = 0.1;
+const UPGRADE_SPEED: f32 = 0.1;
+const UPGRADES: [Upgrade; 6] = [
+    Upgrade::MaxHealth,
+    Upgrade::FireRate,
+    Upgrade::Projectile,
+    Upgrade::Invincibility,
+    Upgrade::Jump,
+    Upgrade::Speed,
+];
+
+const BUTTON_UP_COLOR: Color = Color::rgb(0.85, 0.85, 0.85);
+
+#[derive(Clone, Copy, PartialEq, Eq, Component)]
+pub enum Upgrade {
+    MaxHealth,
+    FireRate,
+    Projectile,
+    Invincibility,
+    Jump,
+    Speed,
+}
+
+impl Upgrade {
+    fn name(self) -> &'static str {
+        match self {
+            Upgrade::MaxHealth => "+1 max health",
+            Upgrade::FireRate => "Faster fire",
+            Upgrade::Projectile => "Extra projectile",
+            Upgrade::Invincibility => "Longer invincibility",
+            Upgrade::Jump => "Higher jump",
+            Upgrade::Speed => "Faster movement",
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct PlayerStats {
+    pub speed: f32,
+    pub fire_rate: f32,
+    pub projectiles: u32,
+    pub jump: f32,
+}
+
+impl Default for PlayerStats {
+    fn default() -> Self {
+        PlayerStats { speed: 1.0, fire_rate: 1.0, projectiles: 0, jump: 1.0 }
+    }
+}
+
+pub struct Kills {
+    count: u32,
+    next: u32,
+    pending: u32,
+}
+
+#[derive(Component)]
+pub struct UpgradeEntity;
+
+pub fn init(mut commands: Commands) {
+    commands.insert_resource(Kills {
+        count: 0,
+        next: UPGRADE_INTERVAL,
+        pending: 0,
+    });
+}
+
+pub fn check(
+    mut state: ResMut<State<GameState>>,
+    mut kills: ResMut<Kills>,
+    mut events: EventReader<Died>,
+    query: Query<(), (With<Enemy>, Without<Dead>)>,
+) {
+    for event in events.iter() {
+        if event.kind != DamageType::Environment
+            && query.get(event.entity).is_ok()
+        {
+            kills.count += 1;
+        }
+    }
+
+    while kills.count >= kills.next {
+        kills.next += UPGRADE_INTERVAL;
+        kills.pending += 1;
+    }
+
+    if kills.pending > 0 && state.push(GameState::Upgrade).is_ok() {
+        kills.pending -= 1;
+    }
+}
+
+pub fn menu(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    query: Query<&Health, With<Player>>,
+) {
+    let health_max =
+        query.get_single().map_or(PLAYER_HEALTH_LIMIT, |health| health.max);
+    let upgrades = UPGRADES
+        .iter()
+        .copied()
+        .filter(|upgrade| {
+            *upgrade != Upgrade::MaxHealth || health_max < PLAYER_HEALTH_LIMIT
+        })
+        .collect::<Vec<_>>();
+
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                ..Default::default()
+            },
+            color: Color::NONE.into(),
+            ..Default::default()
+        })
+        .insert(UpgradeEntity)
+        .with_children(|children| {
+            children
+                .spawn_bundle(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::ColumnReverse,
+                        align_items: AlignItems::Center,
+                        justify_content: JustifyContent::SpaceEvenly,
+                        size: Size::new(Val::Px(1400.0), Val::Px(600.0)),
+                        ..Default::default()
+                    },
+                    color: Color::rgb(0.85, 0.85, 0.85).into(),
+                    ..Default::default()
+                })
+                .with_children(|children| {
+                    children.spawn_bundle(TextBundle {
+                        text: Text {
+                            sections: vec![TextSection {
+                                value: "LEVEL UP".to_string(),
+                                style: TextStyle {
+                                    font: asset_server.load(FONT),
+                                    font_size: 128.0,
+                                    color: Color::BLACK,
+                                },
+                            }],
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    });
+
+                    children
+                        .spawn_bundle(NodeBundle {
+                            style: Style {
+                                flex_direction: FlexDirection::Row,
+                                justify_content: JustifyContent::SpaceEvenly,
+                                size: Size::new(
+                                    Val::Percent(100.0),
+                                    Val::Px(240.0),
+                                ),
+                                ..Default::default()
+                            },
+                            color: Color::NONE.into(),
+                            ..Default::default()
+                        })
+                        .with_children(|children| {
+                            for upgrade in upgrades.choose_multiple(
+                                &mut rand::thread_rng(),
+                                UPGRADE_CHOICES,
+                            ) {
+                                children
+                                    .spawn_bundle(ButtonBundle {
+                                        style: Style {
+                                            align_items: AlignItems::Center,
+                                            justify_content:
+                                                JustifyContent::Center,
+                                            size: Size::new(
+                                                Val::Px(420.0),
+                                                Val::Px(240.0),
+                                            ),
+                                            ..Default::default()
+                                        },
+                                        color: BUTTON_UP_COLOR.into(),
+                                        ..Default::default()
+                                    })
+                                    .insert(*upgrade)
+                                    .with_children(|children| {
+                                        children.spawn_bundle(TextBundle {
+                                            text: Text {
+                                                sections: vec![TextSection {
+                                                    value: upgrade
+                                                        .name()
+                                                        .to_string(),
+                                                    style: TextStyle {
+                                                        font: asset_server
+                                                            .load(FONT),
+                                                        font_size: 48.0,
+                                                        color: Color::BLACK,
+                                                    },
+                                                }],
+                                                ..Default::default()
+                                            },
+                                            ..Default::default()
+                                        });
+                                    });
+                            }
+                        });
+                });
+        });
+}
+
+pub fn select(
+    mut state: ResMut<State<GameState>>,
+    mut button_query: Query<
+        (&mut UiColor, &Interaction, &Upgrade),
+        Changed<Interaction>,
+    >,
+    mut player_query: Query<(&mut Health, &mut PlayerStats), With<Player>>,
+) {
+    for (mut color, interaction, upgrade) in button_query.iter_mut() {
+        if *interaction == Interaction::Hovered && color.0 != Color::GRAY {
+            color.0 = Color::GRAY;
+        } else {
+            color.0 = BUTTON_UP_COLOR;
+        }
+
+        if *interaction != Interaction::Clicked {
+            continue;
+        }
+
+        if let Ok((mut health, mut stats)) = player_query.get_single_mut() {
+            match upgrade {
+                Upgrade::MaxHealth => {
+                    health.max += 1;
+                    health.heal(1);
+                }
+                Upgrade::FireRate => stats.fire_rate += UPGRADE_FIRE_RATE,
+                Upgrade::Projectile => stats.projectiles += 1,
+                Upgrade::Invincibility => {
+                    let invincible = health.invincible();
+                    health.invincibility += UPGRADE_INVINCIBILITY;
+
+                    if !invincible {
+                        health.now = health.invincibility;
+                    }
+                }
+                Upgrade::Jump => stats.jump += UPGRADE_JUMP,
+                Upgrade::Speed => stats.speed += UPGRADE_SPEED,
+            }
+        }
+
+        state.pop().unwrap();
+        break;
+    }
+}
+
+pub fn deinit(
+    mut commands: Commands,
+    query: Query<Entity, With<UpgradeEntity>>,
+) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
@@ -0,0 +1,438 @@
+use std::{f32::consts::PI, ops::Add};
+
+use bevy::prelude::*;
+
+use super::{
+    entity::{GameEntity, GAME_LAYER},
+    player::Player,
+};
+
+const CHARGE_WIDTH: f32 = 96.0;
+const CHARGE_HEIGHT: f32 = 12.0;
+const CHARGE_OFFSET: f32 = 88.0;
+const CHARGE_COLOR: Color = Color::rgb(1.0, 0.8, 0.2);
+const CHARGE_FULL_COLOR: Color = Color::rgb(4.0, 4.0, 4.0);
+
+#[derive(Clone, Copy)]
+pub struct Projectile {
+    pub texture: &'static str,
+    pub size: Vec2,
+    pub color: Color,
+    pub gravity: bool,
+}
+
+#[derive(Clone, Copy, Default)]
+pub struct Modifiers {
+    pub pierce: u32,
+    pub bounce: u32,
+    pub homing: f32,
+    pub explode: f32,
+    pub burn: f32,
+    pub freeze: f32,
+    pub slow: f32,
+    pub poison: f32,
+}
+
+impl Add for Modifiers {
+    type Output = Modifiers;
+
+    fn add(self, other: Modifiers) -> Modifiers {
+        Modifiers {
+            pierce: self.pierce + other.pierce,
+            bounce: self.bounce + other.bounce,
+            homing: self.homing + other.homing,
+            explode: self.explode + other.explode,
+            burn: self.burn + other.burn,
+            freeze: self.freeze + other.freeze,
+            slow: self.slow + other.slow,
+            poison: self.poison + other.poison,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub enum Supply {
+    Unlimited,
+    Magazine { size: u32, ammo: u32, now: f32, max: f32 },
+    Heat { heat: f32, per_shot: f32, cooling: f32, overheated: bool },
+}
+
+impl Supply {
+    fn magazine(size: u32, max: f32) -> Self {
+        Supply::Magazine { size, ammo: size, now: max, max }
+    }
+
+    fn heat(per_shot: f32, cooling: f32) -> Self {
+        Supply::Heat { heat: 0.0, per_shot, cooling, overheated: false }
+    }
+
+    pub fn ready(&self) -> bool {
+        match *self {
+            Supply::Unlimited => true,
+            Supply::Magazine { ammo, now, max, .. } => ammo > 0 && now >= max,
+            Supply::Heat { overheated, .. } => !overheated,
+        }
+    }
+
+    pub fn consume(&mut self) {
+        match self {
+            Supply::Unlimited => {}
+            Supply::Magazine { ammo, now, .. } => {
+                *ammo = ammo.saturating_sub(1);
+
+                if *ammo == 0 {
+                    *now = 0.0;
+                }
+            }
+            Supply::Heat { heat, per_shot, overheated, .. } => {
+                *heat += *per_shot;
+
+                if *heat >= 1.0 {
+                    *heat = 1.0;
+                    *overheated = true;
+                }
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct Charge {
+    pub max: f32,
+    pub damage: u32,
+    pub scale: f32,
+    pub modifiers: Modifiers,
+}
+
+#[derive(Clone, Copy, Component)]
+pub struct Weapon {
+    pub name: &'static str,
+    pub count: u32,
+    pub spread: f32,
+    pub speed: f32,
+    pub damage: u32,
+    pub modifiers: Modifiers,
+    pub knockback: f32,
+    pub hit_stop: u32,
+    pub flash: f32,
+    pub projectile: Projectile,
+    pub supply: Supply,
+    pub charge: Option<Charge>,
+    pub charging: f32,
+    pub now: f32,
+    pub max: f32,
+}
+
+impl Weapon {
+    pub fn pistol() -> Self {
+        Weapon {
+            name: "Pistol",
+            count: 1,
+            spread: 0.0,
+            speed: 1536.0,
+            damage: 1,
+            modifiers: Modifiers::default(),
+            knockback: 384.0,
+            hit_stop: 2,
+            flash: 1.0,
+            projectile: Projectile {
+                texture: "bullet.png",
+                size: Vec2::new(32.0, 32.0),
+                color: Color::WHITE,
+                gravity: false,
+            },
+            supply: Supply::Unlimited,
+            charge: Some(Charge {
+                max: 1.0,
+                damage: 4,
+                scale: 2.5,
+                modifiers: Modifiers {
+                    pierce: 4,
+                    homing: PI,
+                    poison: 4.0,
+                    ..Default::default()
+                },
+            }),
+            charging: 0.0,
+            now: 0.25,
+            max: 0.25,
+        }
+    }
+
+    pub fn shotgun() -> Self {
+        Weapon {
+            name: "Shotgun",
+            count: 6,
+            spread: PI / 6.0,
+            speed: 1280.0,
+            damage: 1,
+            modifiers: Modifiers { slow: 2.0, ..Default::default() },
+            knockback: 256.0,
+            hit_stop: 3,
+            flash: 0.5,
+            projectile: Projectile {
+                texture: "bullet.png",
+                size: Vec2::new(24.0, 24.0),
+                color: Color::rgb(1.0, 0.8, 0.5),
+                gravity: false,
+            },
+            supply: Supply::magazine(2, 1.2),
+            charge: None,
+            charging: 0.0,
+            now: 0.8,
+            max: 0.8,
+        }
+    }
+
+    pub fn machine_gun() -> Self {
+        Weapon {
+            name: "Machine gun",
+            count: 1,
+            spread: PI / 24.0,
+            speed: 1792.0,
+            damage: 1,
+            modifiers: Modifiers::default(),
+            knockback: 128.0,
+            hit_stop: 0,
+            flash: 0.5,
+            projectile: Projectile {
+                texture: "bullet.png",
+                size: Vec2::new(24.0, 24.0),
+                color: Color::rgb(1.0, 1.0, 0.6),
+                gravity: false,
+            },
+            supply: Supply::heat(0.08, 0.5),
+            charge: None,
+            charging: 0.0,
+            now: 0.08,
+            max: 0.08,
+        }
+    }
+
+    pub fn laser() -> Self {
+        Weapon {
+            name: "Laser",
+            count: 1,
+            spread: 0.0,
+            speed: 3072.0,
+            damage: 2,
+            modifiers: Modifiers { pierce: 8, ..Default::default() },
+            knockback: 64.0,
+            hit_stop: 1,
+            flash: 1.0,
+            projectile: Projectile {
+                texture: "bullet.png",
+                size: Vec2::new(96.0, 12.0),
+                color: Color::rgb(0.4, 1.0, 1.0),
+                gravity: false,
+            },
+            supply: Supply::heat(0.3, 0.4),
+            charge: Some(Charge {
+                max: 1.5,
+                damage: 3,
+                scale: 2.0,
+                modifiers: Modifiers {
+                    pierce: 16,
+                    freeze: 2.0,
+                    ..Default::default()
+                },
+            }),
+            charging: 0.0,
+            now: 0.5,
+            max: 0.5,
+        }
+    }
+
+    pub fn grenade_launcher() -> Self {
+        Weapon {
+            name: "Grenade launcher",
+            count: 1,
+            spread: 0.0,
+            speed: 1024.0,
+            damage: 5,
+            modifiers: Modifiers {
+                bounce: 2,
+                explode: 192.0,
+                burn: 3.0,
+                ..Default::default()
+            },
+            knockback: 768.0,
+            hit_stop: 4,
+            flash: 2.0,
+            projectile: Projectile {
+                texture: "bullet.png",
+                size: Vec2::new(40.0, 40.0),
+                color: Color::rgb(0.5, 1.0, 0.5),
+                gravity: true,
+            },
+            supply: Supply::magazine(3, 2.0),
+            charge: None,
+            charging: 0.0,
+            now: 1.0,
+            max: 1.0,
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct Weapons {
+    pub weapons: Vec<Weapon>,
+    pub current: usize,
+}
+
+impl Default for Weapons {
+    fn default() -> Self {
+        Weapons {
+            weapons: vec![
+                Weapon::pistol(),
+                Weapon::shotgun(),
+                Weapon::machine_gun(),
+                Weapon::laser(),
+                Weapon::grenade_launcher(),
+            ],
+            current: 0,
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct ChargeIndicator;
+
+pub fn init(mut commands: Commands) {
+    commands
+        .spawn_bundle(SpriteBundle {
+            sprite: Sprite {
+                color: CHARGE_COLOR,
+                custom_size: Some(Vec2::new(0.0, CHARGE_HEIGHT)),
+                ..Default::default()
+            },
+            visibility: Visibility { is_visible: false },
+            ..Default::default()
+        })
+        .insert(GameEntity)
+        .insert(ChargeIndicator);
+}
+
+pub fn switch(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut query: Query<(&mut Weapon, &mut Weapons), With<Player>>,
+) {
+    let (mut weapon, mut weapons) = if let Ok(result) = query.get_single_mut() {
+        result
+    } else {
+        return;
+    };
+
+    let count = weapons.weapons.len();
+    let mut current = weapons.current;
+
+    for (index, key) in [
+        KeyCode::Key1,
+        KeyCode::Key2,
+        KeyCode::Key3,
+        KeyCode::Key4,
+        KeyCode::Key5,
+    ]
+    .iter()
+    .enumerate()
+    {
+        if index < count && keyboard_input.just_pressed(*key) {
+            current = index;
+        }
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Q) {
+        current = (current + count - 1) % count;
+    }
+
+    if keyboard_input.just_pressed(KeyCode::E) {
+        current = (current + 1) % count;
+    }
+
+    if current != weapons.current {
+        let index = weapons.current;
+        weapons.weapons[index] = *weapon;
+        weapons.current = current;
+        weapon.charging = 0.0;
+
+        let now = weapon.now;
+        *weapon = weapons.weapons[current];
+        weapon.now = weapon.now.max(now).min(weapon.max);
+    }
+}
+
+pub fn charge(
+    player_query: Query<(&Transform, &Weapon), With<Player>>,
+    mut indicator_query: Query<
+        (&mut Transform, &mut Sprite, &mut Visibility),
+        (With<ChargeIndicator>, Without<Player>),
+    >,
+) {
+    let (mut transform, mut sprite, mut visibility) =
+        indicator_query.single_mut();
+
+    let (player_transform, charge, charging) =
+        if let Ok((player_transform, weapon)) = player_query.get_single() {
+            if let Some(charge) = weapon.charge {
+                (player_transform, charge, weapon.charging)
+            } else {
+                visibility.is_visible = false;
+                return;
+            }
+        } else {
+            visibility.is_visible = false;
+            return;
+        };
+
+    let progress = (charging / charge.max).min(1.0);
+
+    visibility.is_visible = progress > 0.0;
+    sprite.custom_size =
+        Some(Vec2::new(CHARGE_WIDTH * progress, CHARGE_HEIGHT));
+    sprite.color =
+        if progress >= 1.0 { CHARGE_FULL_COLOR } else { CHARGE_COLOR };
+    transform.translation = Vec3::new(
+        player_transform.translation.x,
+        player_transform.translation.y + CHARGE_OFFSET,
+        GAME_LAYER,
+    );
+}
+
+pub fn supply(
+    time: Res<Time>,
+    keyboard_input: Res<Input<KeyCode>>,
+    mut query: Query<&mut Weapon, With<Player>>,
+) {
+    let mut weapon = if let Ok(result) = query.get_single_mut() {
+        result
+    } else {
+        return;
+    };
+
+    match &mut weapon.supply {
+        Supply::Unlimited => {}
+        Supply::Magazine { size, ammo, now, max } => {
+            if keyboard_input.just_pressed(KeyCode::R)
+                && *ammo < *size
+                && *now >= *max
+            {
+                *now = 0.0;
+            }
+
+            if *now < *max {
+                *now += time.delta_seconds();
+
+                if *now >= *max {
+                    *ammo = *size;
+                }
+            }
+        }
+        Supply::Heat { heat, cooling, overheated, .. } => {
+            *heat = (*heat - *cooling * time.delta_seconds()).max(0.0);
+
+            if *overheated && *heat == 0.0 {
+                *overheated = false;
+            }
+        }
+    }
+}
@@ -14,6 +14,7 @@ pub struct UnfaireumPlugins;
 pub enum GameState {
     Game,
     GameOver,
+    Upgrade,
 }
 
 impl PluginGroup for UnfaireumPlugins {